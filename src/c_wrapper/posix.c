@@ -3,7 +3,7 @@
 
 typedef void (*callback)(union sigval);
 
-timer_t posix_timer(callback cb, void* data) {
+timer_t posix_timer(clockid_t clock, callback cb, void* data) {
     timer_t id;
     struct sigevent sev = {
         .sigev_notify = SIGEV_THREAD,
@@ -12,7 +12,7 @@ timer_t posix_timer(callback cb, void* data) {
 
     sev.sigev_value.sival_ptr = data;
 
-    if (timer_create(CLOCK_REALTIME, &sev, &id) == -1) {
+    if (timer_create(clock, &sev, &id) == -1) {
         return 0;
     } else {
         return id;
@@ -0,0 +1,117 @@
+//! CPU time limited future
+
+use core::future::Future;
+use core::{fmt, mem, task, time};
+use core::pin::Pin;
+
+fn thread_cpu_time() -> time::Duration {
+    let mut time = mem::MaybeUninit::<libc::timespec>::uninit();
+    unsafe {
+        os_assert!(libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, time.as_mut_ptr()) == 0);
+        let time = time.assume_init();
+        time::Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+}
+
+#[must_use = "CpuTimed does nothing unless polled"]
+///Limiter on CPU time consumed by underlying `Future`
+///
+///Only time spent within `poll` of underlying future is accounted, so waiting on I/O or timers
+///doesn't count toward the limit.
+///Note that limit is checked after each poll, hence future that never yields cannot be interrupted.
+///
+///# Usage
+///
+///```rust, no_run
+///async fn job() {
+///}
+///
+///async fn do_job() {
+///    let mut job = job();
+///    let job = unsafe {
+///        core::pin::Pin::new_unchecked(&mut job)
+///    };
+///
+///    match async_timer::CpuTimed::new(job, core::time::Duration::from_millis(10)).await {
+///        Ok(_) => println!("I'm done!"),
+///        Err(expired) => println!("Job is too greedy: {}", expired),
+///    }
+///}
+///```
+pub struct CpuTimed<'a, F> {
+    fut: Pin<&'a mut F>,
+    limit: time::Duration,
+    consumed: time::Duration,
+}
+
+impl<'a, F: Future> CpuTimed<'a, F> {
+    #[inline]
+    ///Creates new instance with specified limit of CPU time.
+    pub fn new(fut: Pin<&'a mut F>, limit: time::Duration) -> Self {
+        Self {
+            fut,
+            limit,
+            consumed: time::Duration::from_secs(0),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns CPU time consumed by underlying future so far.
+    pub fn consumed(&self) -> time::Duration {
+        self.consumed
+    }
+}
+
+impl<'a, F: Future> Future for CpuTimed<'a, F> {
+    type Output = Result<F::Output, CpuExpired>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.consumed >= this.limit {
+            return task::Poll::Ready(Err(CpuExpired {
+                limit: this.limit,
+                consumed: this.consumed,
+            }));
+        }
+
+        let before = thread_cpu_time();
+        let result = Future::poll(this.fut.as_mut(), ctx);
+        this.consumed += thread_cpu_time().saturating_sub(before);
+
+        match result {
+            task::Poll::Ready(result) => task::Poll::Ready(Ok(result)),
+            task::Poll::Pending => match this.consumed >= this.limit {
+                true => task::Poll::Ready(Err(CpuExpired {
+                    limit: this.limit,
+                    consumed: this.consumed,
+                })),
+                false => task::Poll::Pending,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+///Error when [CpuTimed](struct.CpuTimed.html) exceeds its limit
+pub struct CpuExpired {
+    ///Limit of CPU time.
+    pub limit: time::Duration,
+    ///CPU time consumed by future.
+    pub consumed: time::Duration,
+}
+
+#[cfg(feature = "std")]
+impl crate::std::error::Error for CpuExpired {}
+
+impl fmt::Debug for CpuExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for CpuExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Future consumed {:?} of CPU time, exceeding limit of {:?}", self.consumed, self.limit)
+    }
+}
@@ -0,0 +1,136 @@
+//! Extension traits
+
+#[cfg(any(unix, windows))]
+use std::time;
+#[cfg(target_arch = "wasm32")]
+use web_time as time;
+#[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+use crate::timer::custom::time;
+use core::{cmp, task};
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::timer::Timer;
+use crate::timer::Platform as PlatformTimer;
+use crate::Timeout;
+
+#[inline]
+pub(crate) fn until(deadline: time::Instant) -> time::Duration {
+    //Timer cannot be armed with zero timeout, so elapsed deadline expires as soon as possible
+    cmp::max(deadline.saturating_duration_since(time::Instant::now()), time::Duration::from_nanos(1))
+}
+
+///Extension methods for `Future`
+///
+///Methods without `_with` suffix use [Platform](timer/type.Platform.html) timer.
+///
+///## Usage
+///
+///```rust, no_run
+///use async_timer::FutureExt;
+///
+///use core::time;
+///
+///async fn job() {
+///}
+///
+///async fn do_job() {
+///    match job().delay_start(time::Duration::from_millis(100)).timeout(time::Duration::from_secs(1)).await {
+///        Ok(_) => println!("I'm done!"),
+///        Err(error) => println!("Job failed: {}", error),
+///    }
+///}
+///```
+pub trait FutureExt: Future + Sized {
+    #[inline(always)]
+    ///Limits time to wait for the future to resolve.
+    fn timeout(self, timeout: time::Duration) -> Timeout<Self, PlatformTimer> {
+        self.timeout_with(timeout)
+    }
+
+    #[inline(always)]
+    ///Limits time to wait for the future to resolve, using specified `Timer`.
+    fn timeout_with<T: Timer>(self, timeout: time::Duration) -> Timeout<Self, T> {
+        Timeout::new(self, timeout)
+    }
+
+    #[inline(always)]
+    ///Limits time to wait for the future to resolve until `deadline`.
+    fn timeout_at(self, deadline: time::Instant) -> Timeout<Self, PlatformTimer> {
+        self.timeout_at_with(deadline)
+    }
+
+    #[inline(always)]
+    ///Limits time to wait for the future to resolve until `deadline`, using specified `Timer`.
+    fn timeout_at_with<T: Timer>(self, deadline: time::Instant) -> Timeout<Self, T> {
+        Timeout::new_at(self, deadline)
+    }
+
+    #[inline(always)]
+    ///Delays first poll of the future by `delay`.
+    fn delay_start(self, delay: time::Duration) -> DelayStart<Self, PlatformTimer> {
+        self.delay_start_with(delay)
+    }
+
+    #[inline(always)]
+    ///Delays first poll of the future by `delay`, using specified `Timer`.
+    fn delay_start_with<T: Timer>(self, delay: time::Duration) -> DelayStart<Self, T> {
+        DelayStart::new(self, delay)
+    }
+}
+
+impl<F: Future> FutureExt for F {}
+
+#[must_use = "DelayStart does nothing unless polled"]
+///Future which starts polling underlying future only after delay expires.
+pub struct DelayStart<F, T=PlatformTimer> {
+    fut: F,
+    delay: T,
+    is_started: bool,
+}
+
+impl<F: Future, T: Timer> DelayStart<F, T> {
+    ///Creates new instance with specified delay
+    pub fn new(fut: F, delay: time::Duration) -> Self {
+        Self {
+            fut,
+            delay: T::new(delay),
+            is_started: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns whether delay has expired and underlying future is being polled.
+    pub fn is_started(&self) -> bool {
+        self.is_started
+    }
+
+    #[inline(always)]
+    ///Consumes self, returning underlying future.
+    pub fn into_inner(self) -> F {
+        self.fut
+    }
+}
+
+impl<F: Future, T: Timer> Future for DelayStart<F, T> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        //`fut` is pinned structurally, while `Timer` is `Unpin`
+        let this = unsafe {
+            self.get_unchecked_mut()
+        };
+
+        if !this.is_started {
+            match Future::poll(Pin::new(&mut this.delay), ctx) {
+                task::Poll::Pending => return task::Poll::Pending,
+                task::Poll::Ready(()) => this.is_started = true,
+            }
+        }
+
+        let fut = unsafe {
+            Pin::new_unchecked(&mut this.fut)
+        };
+        Future::poll(fut, ctx)
+    }
+}
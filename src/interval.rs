@@ -1,21 +1,108 @@
 //!Interval module
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(unix, windows))]
 use std::time;
 #[cfg(target_arch = "wasm32")]
 use web_time as time;
+#[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+use crate::timer::custom::time;
 use core::task;
 use core::future::Future;
 use core::pin::Pin;
 
+use crate::ext::until;
 use crate::timer::Timer;
 use crate::timer::Platform as PlatformTimer;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Describes how [Interval](struct.Interval.html) schedules next tick when it is late.
+pub enum MissedTickBehavior {
+    ///Missed ticks are fired as soon as possible, until interval catches up with schedule.
+    Burst,
+    ///Schedule is restarted from the moment of late tick, i.e. next tick happens after full period.
+    Delay,
+    ///Missed ticks are skipped, and next tick happens at the next multiple of period since
+    ///schedule's start.
+    ///
+    ///This is default behavior.
+    Skip,
+}
+
+impl MissedTickBehavior {
+    //Returns deadline of the tick following `scheduled` one, which expired at `now`.
+    pub(crate) fn next(self, scheduled: time::Instant, now: time::Instant, interval: time::Duration) -> time::Instant {
+        match self {
+            MissedTickBehavior::Burst => scheduled + interval,
+            MissedTickBehavior::Delay => now + interval,
+            MissedTickBehavior::Skip => match now.checked_duration_since(scheduled) {
+                Some(delayed) => now + interval - time::Duration::from_nanos((delayed.as_nanos() % interval.as_nanos()) as _),
+                None => scheduled + interval,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Tick of [Interval](struct.Interval.html)
+pub struct Tick {
+    ///Instant at which tick was scheduled.
+    pub scheduled: time::Instant,
+    ///Time by which tick was late in relation to `scheduled`.
+    pub lateness: time::Duration,
+}
+
+impl Default for MissedTickBehavior {
+    #[inline(always)]
+    fn default() -> Self {
+        MissedTickBehavior::Skip
+    }
+}
+
+#[cfg(any(unix, windows, target_arch = "wasm32"))]
+#[derive(Clone, Copy)]
+struct Alignment {
+    offset: time::Duration,
+    //Number of periods since origin, at which pending tick is scheduled
+    boundary: u128,
+}
+
+#[cfg(any(unix, windows, target_arch = "wasm32"))]
+impl Alignment {
+    //Returns instant of the next boundary according to wall clock.
+    //
+    //Wall clock is read on each call, so that schedule is re-aligned after clock adjustments.
+    fn next(&mut self, period: time::Duration) -> time::Instant {
+        let now = time::Instant::now();
+        let since_epoch = time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap_or_default();
+        let since_origin = since_epoch.saturating_sub(self.offset).as_nanos();
+        let period = period.as_nanos();
+
+        let mut boundary = since_origin / period + 1;
+        //Timer may expire slightly ahead of wall clock, in which case pending boundary is not passed yet.
+        if boundary == self.boundary {
+            boundary += 1;
+        }
+        self.boundary = boundary;
+
+        now + time::Duration::from_nanos((boundary * period - since_origin) as u64)
+    }
+}
+
+#[inline(always)]
+///Creates interval with default Platform timer, with first tick at `start`.
+pub fn interval_at(start: time::Instant, interval: time::Duration) -> Interval<PlatformTimer> {
+    Interval::platform_new_at(start, interval)
+}
+
 ///Periodic Timer
 ///
 ///On each completion, underlying timer is restarted and therefore `Future` can be polled once
 ///more.
 ///
+///Ticks are scheduled at `start + n * interval` and timer is armed using absolute deadline, when
+///supported (see [Timer::restart_at](timer/trait.Timer.html#method.restart_at)), hence error does
+///not accumulate over time.
+///
 ///## Usage
 ///
 ///```rust, no_run
@@ -32,13 +119,30 @@ use crate::timer::Platform as PlatformTimer;
 ///        times += 1;
 ///    }
 ///}
+///
+///async fn do_immediately() {
+///    //First tick happens right away
+///    let mut interval = async_timer::interval_at(std::time::Instant::now(), core::time::Duration::from_secs(1));
+///
+///    loop {
+///        let tick = interval.tick().await;
+///        println!("Tick scheduled at {:?} is late by {:?}", tick.scheduled, tick.lateness);
+///        job().await;
+///    }
+///}
 ///```
 #[must_use = "Interval does nothing unless polled"]
 pub struct Interval<T=PlatformTimer> {
     timer: T,
     ///Timer interval, change to this value will be reflected on next restart of timer.
+    ///
+    ///Use [set_period](struct.Interval.html#method.set_period) to apply it to the pending tick.
     pub interval: time::Duration,
+    //Instant at which pending tick is scheduled
     finish_at: time::Instant,
+    missed_tick_behavior: MissedTickBehavior,
+    #[cfg(any(unix, windows, target_arch = "wasm32"))]
+    alignment: Option<Alignment>,
 }
 
 impl Interval {
@@ -47,33 +151,184 @@ impl Interval {
     pub fn platform_new(interval: time::Duration) -> Self {
         Interval::<PlatformTimer>::new(interval)
     }
+
+    #[inline(always)]
+    ///Creates new instance using platform timer, with first tick at `start`
+    pub fn platform_new_at(start: time::Instant, interval: time::Duration) -> Self {
+        Interval::<PlatformTimer>::new_at(start, interval)
+    }
+
+    #[cfg(any(unix, windows, target_arch = "wasm32"))]
+    #[inline(always)]
+    ///Creates new instance using platform timer, with ticks aligned to wall clock.
+    ///
+    ///See [new_aligned](struct.Interval.html#method.new_aligned) for details.
+    pub fn platform_new_aligned(interval: time::Duration, offset: time::Duration) -> Self {
+        Interval::<PlatformTimer>::new_aligned(interval, offset)
+    }
 }
 
 impl<T: Timer> Interval<T> {
+    #[inline(always)]
     ///Creates new instance with specified timer type.
+    ///
+    ///First tick happens after `interval`.
     pub fn new(interval: time::Duration) -> Self {
+        Self::new_at(time::Instant::now() + interval, interval)
+    }
+
+    ///Creates new instance with specified timer type, with first tick at `start`.
+    ///
+    ///If `start` is already reached, first tick happens immediately.
+    pub fn new_at(start: time::Instant, interval: time::Duration) -> Self {
+        assert_time!(interval);
+        Self {
+            timer: T::new(until(start)),
+            finish_at: start,
+            interval,
+            missed_tick_behavior: MissedTickBehavior::Skip,
+            #[cfg(any(unix, windows, target_arch = "wasm32"))]
+            alignment: None,
+        }
+    }
+
+    #[cfg(any(unix, windows, target_arch = "wasm32"))]
+    ///Creates new instance with specified timer type, with ticks aligned to wall clock.
+    ///
+    ///Ticks happen at `UNIX_EPOCH + offset + n * interval` according to system clock, e.g.
+    ///interval of 10 seconds ticks at :00, :10, :20 seconds and so on.
+    ///
+    ///Alignment is re-calculated on each tick, following adjustments of system clock.
+    ///Missed ticks are always skipped, regardless of [MissedTickBehavior](enum.MissedTickBehavior.html).
+    pub fn new_aligned(interval: time::Duration, offset: time::Duration) -> Self {
+        assert_time!(interval);
+        let mut alignment = Alignment {
+            offset,
+            boundary: 0,
+        };
+        let start = alignment.next(interval);
+
         Self {
-            timer: T::new(interval),
-            finish_at: time::Instant::now() + interval,
+            timer: T::new(until(start)),
+            finish_at: start,
             interval,
+            missed_tick_behavior: MissedTickBehavior::Skip,
+            alignment: Some(alignment),
         }
     }
 
+    #[cfg(any(unix, windows, target_arch = "wasm32"))]
+    #[inline(always)]
+    fn next_aligned(&mut self, is_reset: bool) -> Option<time::Instant> {
+        let interval = self.interval;
+        self.alignment.as_mut().map(|alignment| {
+            if is_reset {
+                alignment.boundary = 0;
+            }
+            alignment.next(interval)
+        })
+    }
+
+    #[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+    #[inline(always)]
+    fn next_aligned(&mut self, _: bool) -> Option<time::Instant> {
+        None
+    }
+
+    #[inline(always)]
+    ///Returns behavior when tick is missed.
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    #[inline(always)]
+    ///Sets behavior when tick is missed, which is applied on next restart of timer.
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
     #[inline(always)]
     ///Stops interval
     pub fn cancel(&mut self) {
         self.timer.cancel()
     }
 
-    ///Restarts interval
+    #[inline(always)]
+    ///Returns instant at which pending tick is scheduled.
+    pub fn deadline(&self) -> time::Instant {
+        self.finish_at
+    }
+
+    ///Resets schedule, so that pending tick happens after full period from now.
+    ///
+    ///Aligned interval is re-aligned instead, i.e. pending tick happens at the next boundary.
+    pub fn reset(&mut self) {
+        let deadline = match self.next_aligned(true) {
+            Some(deadline) => deadline,
+            None => time::Instant::now() + self.interval,
+        };
+        self.reset_at(deadline)
+    }
+
+    ///Resets schedule, so that pending tick happens at `deadline`.
+    ///
+    ///Subsequent ticks are scheduled relative to `deadline`.
+    pub fn reset_at(&mut self, deadline: time::Instant) {
+        self.finish_at = deadline;
+        self.timer.restart_at(deadline);
+    }
+
+    ///Changes period of the interval, including pending tick.
+    ///
+    ///Pending tick is re-scheduled to happen after new period since previous tick, or immediately,
+    ///if that moment has already passed.
+    ///
+    ///Aligned interval is re-aligned to the new period instead.
+    pub fn set_period(&mut self, period: time::Duration) {
+        assert_time!(period);
+        let previous = self.finish_at.checked_sub(self.interval).unwrap_or(self.finish_at);
+        self.interval = period;
+        let deadline = match self.next_aligned(true) {
+            Some(deadline) => deadline,
+            None => previous + period,
+        };
+        self.reset_at(deadline);
+    }
+
+    ///Restarts interval, scheduling next tick according to [MissedTickBehavior](enum.MissedTickBehavior.html).
     pub fn restart(&mut self) {
+        self.expire();
+    }
+
+    //Restarts interval, returning expired tick.
+    fn expire(&mut self) -> Tick {
         let now = time::Instant::now();
+        let scheduled = self.finish_at;
+        let lateness = now.saturating_duration_since(scheduled);
 
-        let interval = match now.checked_duration_since(self.finish_at) {
-            Some(delayed) => self.interval - time::Duration::from_nanos((delayed.as_nanos() % self.interval.as_nanos()) as _),
-            None => self.interval
+        self.finish_at = match self.next_aligned(false) {
+            Some(deadline) => deadline,
+            None => self.missed_tick_behavior.next(scheduled, now, self.interval),
         };
-        self.timer.restart(interval);
+        //Schedule is kept in absolute time, so that latency of each tick doesn't accumulate
+        self.timer.restart_at(self.finish_at);
+
+        Tick {
+            scheduled,
+            lateness,
+        }
+    }
+
+    fn poll_tick(&mut self, ctx: &mut task::Context) -> task::Poll<Tick> {
+        if !self.timer.is_ticking() && !self.timer.is_expired() {
+            //Timer starts on first poll, so timeout should be adjusted to the time passed since creation
+            self.timer.restart_at(self.finish_at);
+        }
+
+        match Future::poll(Pin::new(&mut self.timer), ctx) {
+            task::Poll::Ready(()) => task::Poll::Ready(self.expire()),
+            task::Poll::Pending => task::Poll::Pending,
+        }
     }
 
     #[inline(always)]
@@ -81,18 +336,37 @@ impl<T: Timer> Interval<T> {
     pub fn wait<'a>(&'a mut self) -> impl Future<Output=()> + 'a {
         self
     }
+
+    #[inline(always)]
+    ///Returns future for next expiration, which resolves to the [Tick](struct.Tick.html).
+    pub fn tick<'a>(&'a mut self) -> impl Future<Output=Tick> + 'a {
+        core::future::poll_fn(move |ctx| self.poll_tick(ctx))
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: Timer> futures_core_03::Stream for Interval<T> {
+    ///Instant at which tick was scheduled.
+    type Item = time::Instant;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Option<Self::Item>> {
+        self.poll_tick(ctx).map(|tick| Some(tick.scheduled))
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: Timer> futures_core_03::stream::FusedStream for Interval<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        //Interval never ends
+        false
+    }
 }
 
 impl<T: Timer> Future for &'_ mut Interval<T> {
     type Output = ();
 
     fn poll(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
-        match Future::poll(Pin::new(&mut self.timer), ctx) {
-            task::Poll::Ready(()) => {
-                self.restart();
-                task::Poll::Ready(())
-            },
-            task::Poll::Pending => task::Poll::Pending,
-        }
+        self.poll_tick(ctx).map(|_| ())
     }
 }
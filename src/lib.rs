@@ -11,20 +11,35 @@
 //!
 //! - [Timer](timer/trait.Timer.html) interface to one-shot [Platform Timer](timer/type.Platform.html), may require event loop.
 //! - [SyncTimer](timer/trait.SyncTimer.html) interface to one-shot [Platform Timer](timer/type.SyncPlatform.html), does not require event loop.
+//! - [PrecisionTimer](timer/struct.PrecisionTimer.html) wrapper over timer, that spins for the last moments before deadline to achieve sub-millisecond accuracy.
+//! - [BackendTimer](timer/struct.BackendTimer.html) timer over user provided [TimerBackend](timer/trait.TimerBackend.html), allowing to implement custom timers.
+//! - [CalibratedTimer](timer/struct.CalibratedTimer.html) wrapper over timer, that is armed earlier by typical wakeup latency, measured by [Calibrator](timer/struct.Calibrator.html).
 //!
 //! ## Primitives
 //!
 //! - [Timed](struct.Timed.html) - A wrapper over future that allows to limit time for the future to resolve.
+//! - [Timeout](struct.Timeout.html) - Owned version of `Timed`, which doesn't require to pin future.
+//! - [CpuTimed](struct.CpuTimed.html) - A wrapper over future that allows to limit CPU time consumed by the future. Unix only.
+//! - [Sleep](struct.Sleep.html) - Future that resolves at deadline, created by [sleep](fn.sleep.html) and [sleep_until](fn.sleep_until.html).
+//! - [SyncInterval](struct.SyncInterval.html) - Periodic timer, that invokes callback on each tick without async runtime.
+//! - [FutureExt](trait.FutureExt.html) - Extension methods to limit or delay futures.
 //! - [Interval](struct.Interval.html) - Periodic timer, that on each completition returns itself to poll once again with the same interval.
 //!
 //! ## Features
 //!
-//! - `tokio1` - Enables event loop based timers using tokio, providing higher resolution timers on unix platforms.
+//! - `tokio1` - Enables event loop based timers using tokio, providing higher resolution timers on unix platforms. Outside of tokio runtime timers fall back to `SyncPlatform`.
+//! - `async-io` - Enables event loop based timers using `async-io` (e.g. `smol`, `async-std`). `tokio1` takes priority when both are enabled.
+//! - `mio` - Implements `mio::event::Source` for `RawTimer` and provides `MioTimer` for hand written `mio` event loops.
+//! - `custom` - Enables `CustomTimer` driven by application's monotonic counter and alarm, used as platform timer on targets without OS.
+//! - `io-uring` - Enables `UringTimer` on Linux, which submits timeouts to shared `io_uring` instead of creating timer per instance.
+//! - `stream` - Implements `futures_core::Stream` for `Interval`, yielding scheduled `Instant` of each tick, and `FusedFuture` for `Sleep` and timers.
 //! - `c_wrapper` - Uses C shim to create bindings to platform API, which may be more reliable than `libc`.
-//! - `std` - Enables usage of std types (e.g. Error)
+//! - `std` - Enables usage of std types (e.g. Error), `ReactorTimer` to plug custom event loop on unix platforms and `epoll` based `SyncPlatform` on Linux.
 #![warn(missing_docs)]
+//Targets without OS have no `std`
+#![cfg_attr(not(any(unix, windows, target_arch = "wasm32")), no_std)]
 
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::style, clippy::needless_lifetimes))]
+#![allow(clippy::style, clippy::needless_lifetimes)]
 
 extern crate alloc;
 #[cfg(feature = "std")]
@@ -39,12 +54,32 @@ mod utils;
 pub mod state;
 pub mod timer;
 mod timed;
+mod timeout;
+#[cfg(unix)]
+mod cpu_timed;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
 mod interval;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+mod sync_interval;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+mod ext;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+mod sleep;
 
 pub use state::Callback;
 pub use timer::{SyncTimer, Timer, new_sync_timer, new_timer};
 pub use timed::{Timed, Expired};
-pub use interval::Interval;
+pub use timeout::{Timeout, Elapsed};
+#[cfg(unix)]
+pub use cpu_timed::{CpuTimed, CpuExpired};
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+pub use interval::{Interval, MissedTickBehavior, Tick, interval_at};
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+pub use ext::{FutureExt, DelayStart};
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+pub use sync_interval::SyncInterval;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+pub use sleep::{Sleep, sleep, sleep_until};
 
 #[inline(always)]
 ///Creates timed future with default Platform timer.
@@ -52,6 +87,20 @@ pub fn timed<'a, F: Future>(fut: Pin<&'a mut F>, timeout: time::Duration) -> Tim
     Timed::platform_new(fut, timeout)
 }
 
+#[inline(always)]
+///Creates timeout future with default Platform timer, taking ownership of the future.
+pub fn timeout<F: Future>(fut: F, timeout: time::Duration) -> Timeout<F, timer::Platform> {
+    Timeout::platform_new(fut, timeout)
+}
+
+#[cfg(unix)]
+#[inline(always)]
+///Creates future, limited by CPU time it consumes while being polled.
+pub fn cpu_timed<'a, F: Future>(fut: Pin<&'a mut F>, limit: time::Duration) -> CpuTimed<'a, F> {
+    CpuTimed::new(fut, limit)
+}
+
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
 #[inline(always)]
 ///Creates interval with default Platform timer.
 pub fn interval(interval: time::Duration) -> Interval<timer::Platform> {
@@ -0,0 +1,113 @@
+//! Sleep future
+
+#[cfg(any(unix, windows))]
+use std::time;
+#[cfg(target_arch = "wasm32")]
+use web_time as time;
+#[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+use crate::timer::custom::time;
+use core::task;
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::ext::until;
+use crate::timer::Timer;
+use crate::timer::Platform as PlatformTimer;
+
+#[inline(always)]
+///Creates future that resolves after `duration`, using default Platform timer.
+pub fn sleep(duration: time::Duration) -> Sleep<PlatformTimer> {
+    Sleep::platform_new(time::Instant::now() + duration)
+}
+
+#[inline(always)]
+///Creates future that resolves at `deadline`, using default Platform timer.
+pub fn sleep_until(deadline: time::Instant) -> Sleep<PlatformTimer> {
+    Sleep::platform_new(deadline)
+}
+
+#[must_use = "Sleep does nothing unless polled"]
+///Future that resolves once deadline is reached
+///
+///Unlike plain timer, it is bound to deadline rather than timeout, hence time before first poll
+///is accounted for.
+///
+///## Usage
+///
+///```rust, no_run
+///use core::time;
+///
+///async fn job() {
+///    let mut sleep = async_timer::sleep(time::Duration::from_secs(1));
+///    (&mut sleep).await;
+///
+///    let deadline = sleep.deadline() + time::Duration::from_secs(1);
+///    sleep.reset(deadline);
+///    sleep.await;
+///}
+///```
+pub struct Sleep<T=PlatformTimer> {
+    timer: T,
+    deadline: time::Instant,
+}
+
+impl Sleep {
+    #[inline(always)]
+    ///Creates new instance using platform timer
+    pub fn platform_new(deadline: time::Instant) -> Self {
+        Sleep::<PlatformTimer>::new(deadline)
+    }
+}
+
+impl<T: Timer> Sleep<T> {
+    ///Creates new instance, which expires at `deadline`.
+    pub fn new(deadline: time::Instant) -> Self {
+        Self {
+            timer: T::new(until(deadline)),
+            deadline,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns deadline of the sleep.
+    pub fn deadline(&self) -> time::Instant {
+        self.deadline
+    }
+
+    #[inline(always)]
+    ///Returns whether deadline has been reached.
+    pub fn is_elapsed(&self) -> bool {
+        self.timer.is_expired()
+    }
+
+    ///Resets sleep to new deadline.
+    ///
+    ///If sleep is already elapsed, it can be awaited once again.
+    pub fn reset(&mut self, deadline: time::Instant) {
+        self.deadline = deadline;
+        self.timer.restart(until(deadline));
+    }
+}
+
+impl<T: Timer> Future for Sleep<T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if !this.timer.is_ticking() && !this.timer.is_expired() {
+            //Timer starts on first poll, so timeout should be adjusted to the time passed since creation
+            this.timer.restart(until(this.deadline));
+        }
+
+        Future::poll(Pin::new(&mut this.timer), ctx)
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: Timer> futures_core_03::future::FusedFuture for Sleep<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        self.is_elapsed()
+    }
+}
@@ -2,7 +2,10 @@
 
 use core::{ptr, task, hint, mem};
 use core::cell::UnsafeCell;
-use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+use crate::alloc::sync::Arc;
+use crate::alloc::task::Wake;
 
 #[cold]
 fn should_not_clone(_: *const()) -> task::RawWaker {
@@ -51,8 +54,10 @@ const REGISTERING: u8 = 0b01;
 /// The waker currently registered with the `AtomicWaker` cell is being woken.
 const WAKING: u8 = 0b10;
 
-#[doc(hidden)]
-/// Atomic waker used by `TimerState`
+///Atomic waker used by `TimerState`
+///
+///Stores single `Waker`, which can be registered and woken concurrently.
+///Custom [Callback](trait.Callback.html) implementations register their `Waker` with it.
 pub struct AtomicWaker {
     state: AtomicU8,
     waker: UnsafeCell<task::Waker>,
@@ -133,22 +138,26 @@ macro_rules! impl_register {
 }
 
 impl AtomicWaker {
-    fn new() -> Self {
+    ///Creates new instance with no-op waker.
+    pub fn new() -> Self {
         Self {
             state: AtomicU8::new(WAITING),
             waker: UnsafeCell::new(noop::waker()),
         }
     }
 
-    ///This is the same function as `register` but working with owned version.
-    fn register(&self, waker: task::Waker) {
+    ///Registers owned `Waker`, replacing previous one.
+    pub fn register(&self, waker: task::Waker) {
         impl_register!(self(waker) {
             //unconditionally store since we already have ownership
             *self.waker.get() = waker;
         });
     }
 
-    fn register_ref(&self, waker: &task::Waker) {
+    ///Registers `Waker`, replacing previous one.
+    ///
+    ///Waker is cloned only if it would not wake the same task as currently registered one.
+    pub fn register_ref(&self, waker: &task::Waker) {
         impl_register!(self(waker) {
             // Lock acquired, update the waker cell
             if !(*self.waker.get()).will_wake(waker) {
@@ -158,7 +167,21 @@ impl AtomicWaker {
         });
     }
 
-    fn wake(&self) {
+    ///Wakes registered `Waker`, keeping it registered.
+    ///
+    ///Does nothing if `Waker` is being registered concurrently.
+    pub fn wake_by_ref(&self) {
+        //Unlike `wake`, registration is not interrupted, as it would discard new waker.
+        if self.state.compare_exchange(WAITING, WAKING, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+            unsafe {
+                (*self.waker.get()).wake_by_ref();
+            }
+            self.state.fetch_and(!WAKING, Ordering::Release);
+        }
+    }
+
+    ///Wakes registered `Waker`, if any, leaving no-op waker in its place.
+    pub fn wake(&self) {
         // AcqRel ordering is used in order to acquire the value of the `task`
         // cell as well as to establish a `release` ordering with whatever
         // memory the `AtomicWaker` is associated with.
@@ -191,12 +214,22 @@ impl AtomicWaker {
     }
 }
 
+impl Default for AtomicWaker {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 unsafe impl Send for AtomicWaker {}
 unsafe impl Sync for AtomicWaker {}
 
+//Lowest bit of `TimerState::woken`, while the rest is generation incremented on reset.
+const WOKEN: usize = 1;
+
 ///Timer's state
 pub struct TimerState {
-    woken: AtomicBool,
+    woken: AtomicUsize,
     inner: AtomicWaker,
 }
 
@@ -204,7 +237,7 @@ impl TimerState {
     ///Initializes state.
     pub fn new() -> Self {
         Self {
-            woken: AtomicBool::new(false),
+            woken: AtomicUsize::new(0),
             inner: AtomicWaker::new(),
         }
     }
@@ -216,19 +249,29 @@ impl TimerState {
     ///with `TimerState` or not. It is not intended for user
     ///to call `is_done` before  `register`
     pub fn is_done(&self) -> bool {
-        self.woken.load(Ordering::Acquire)
+        self.woken.load(Ordering::Acquire) & WOKEN == WOKEN
+    }
+
+    #[inline]
+    ///Returns current generation of the state, which is changed on each reset.
+    ///
+    ///Timer implementation should obtain it when timer is armed, in order to notify via
+    ///[wake_generation](#method.wake_generation).
+    pub fn generation(&self) -> usize {
+        self.woken.load(Ordering::Acquire) & !WOKEN
     }
 
     #[inline]
     ///Resets state, allowing to wake once again.
     pub fn reset(&self) {
-        self.woken.store(false, Ordering::Release);
+        //Next generation, with `WOKEN` cleared
+        let _ = self.woken.fetch_update(Ordering::AcqRel, Ordering::Acquire, |woken| Some((woken | WOKEN).wrapping_add(1)));
     }
 
     #[inline]
     ///Informs that timer is cancel, therefore no further callbacks to be passed
     pub fn cancel(&self) {
-        self.woken.store(true, Ordering::Release);
+        self.woken.fetch_or(WOKEN, Ordering::Release);
     }
 
     #[inline]
@@ -243,8 +286,20 @@ impl TimerState {
     ///Notifies underlying `Waker`
     ///
     ///After that `Waker` is no longer registered with `TimerState`
-    pub(crate) fn wake(&self) {
-        if !self.woken.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).unwrap_or_else(|err| err) {
+    ///
+    ///Must be called by timer implementation on expiration. Subsequent calls have no effect
+    ///until state is reset.
+    pub fn wake(&self) {
+        if self.woken.fetch_or(WOKEN, Ordering::SeqCst) & WOKEN == 0 {
+            self.inner.wake();
+        }
+    }
+
+    ///Notifies underlying `Waker`, unless state has been reset since `generation` was obtained.
+    ///
+    ///Used by timers that cannot discard expiration, which is already in flight on restart.
+    pub fn wake_generation(&self, generation: usize) {
+        if self.woken.compare_exchange(generation, generation | WOKEN, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
             self.inner.wake();
         }
     }
@@ -255,7 +310,7 @@ impl TimerState {
 ///It is guaranteed that callback is invoked only once, unless `Timer` is restarted or
 ///`TimerState::reset` is called(happens when timer is restarted)
 pub trait Callback {
-    #[doc(hidden)]
+    ///Registers callback with `AtomicWaker` of timer's state.
     fn register(self, waker: &AtomicWaker);
 }
 
@@ -278,3 +333,89 @@ impl Callback for fn() {
         waker.register(plain_fn::waker(self));
     }
 }
+
+struct FnWaker(Arc<dyn Fn() + Send + Sync>);
+
+impl Wake for FnWaker {
+    #[inline(always)]
+    fn wake(self: Arc<Self>) {
+        (self.0)()
+    }
+
+    #[inline(always)]
+    fn wake_by_ref(self: &Arc<Self>) {
+        (self.0)()
+    }
+}
+
+///Invokes closure on expiration.
+///
+///Closure is wrapped into allocated `Waker`, which is freed by timer's callback on expiration.
+///As callback of `PosixTimer` without `c_wrapper` is signal handler, this is not async-signal-safe,
+///therefore it must not be used with such timer. Use `fn()` or `&Waker`, kept alive by caller, instead.
+impl Callback for Arc<dyn Fn() + Send + Sync> {
+    fn register(self, waker: &AtomicWaker) {
+        waker.register(task::Waker::from(Arc::new(FnWaker(self))));
+    }
+}
+
+#[cfg(any(unix, windows))]
+struct ThreadWaker(std::thread::Thread);
+
+#[cfg(any(unix, windows))]
+impl Wake for ThreadWaker {
+    #[inline(always)]
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+
+    #[inline(always)]
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+#[cfg(any(unix, windows))]
+///Creates `Waker` that unparks current thread.
+pub(crate) fn thread_waker() -> task::Waker {
+    task::Waker::from(Arc::new(ThreadWaker(std::thread::current())))
+}
+
+#[cfg(any(unix, windows))]
+///Unparks thread on expiration.
+///
+///Thread is wrapped into allocated `Waker`, which is freed by timer's callback on expiration.
+///As callback of `PosixTimer` without `c_wrapper` is signal handler, this is not async-signal-safe,
+///therefore it must not be used with such timer. Use `fn()` or `&Waker`, kept alive by caller, instead.
+impl Callback for std::thread::Thread {
+    fn register(self, waker: &AtomicWaker) {
+        waker.register(task::Waker::from(Arc::new(ThreadWaker(self))));
+    }
+}
+
+#[cfg(any(unix, windows))]
+struct SenderWaker(std::sync::Mutex<std::sync::mpsc::Sender<()>>);
+
+#[cfg(any(unix, windows))]
+impl Wake for SenderWaker {
+    #[inline(always)]
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        //Receiver may be gone already, which is not an error for timer.
+        let _ = self.0.lock().unwrap_or_else(|error| error.into_inner()).send(());
+    }
+}
+
+#[cfg(any(unix, windows))]
+///Sends `()` on expiration.
+///
+///Sending locks `Mutex` and may allocate, which is not async-signal-safe, therefore it must not
+///be used with `PosixTimer` without `c_wrapper`, as its callback is invoked from signal handler.
+impl Callback for std::sync::mpsc::Sender<()> {
+    fn register(self, waker: &AtomicWaker) {
+        waker.register(task::Waker::from(Arc::new(SenderWaker(std::sync::Mutex::new(self)))));
+    }
+}
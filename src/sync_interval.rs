@@ -0,0 +1,280 @@
+//! Interval driven by timer's callback
+
+#[cfg(any(unix, windows))]
+use std::time;
+#[cfg(target_arch = "wasm32")]
+use web_time as time;
+#[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+use crate::timer::custom::time;
+use core::{hint, task};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::alloc::sync::Arc;
+use crate::alloc::task::Wake;
+use crate::interval::MissedTickBehavior;
+use crate::state::{AtomicWaker, Callback};
+use crate::timer::SyncTimer;
+use crate::timer::SyncPlatform as SyncPlatformTimer;
+
+struct Schedule {
+    interval: time::Duration,
+    finish_at: time::Instant,
+    missed_tick_behavior: MissedTickBehavior,
+    //Whether interval's waker has been registered with timer
+    is_registered: bool,
+}
+
+struct Inner<T> {
+    timer: UnsafeCell<T>,
+    schedule: UnsafeCell<Schedule>,
+    //Guards `timer` and `schedule`
+    lock: AtomicBool,
+    //Set by callback, when timer is to be re-armed
+    is_pending: AtomicBool,
+    is_running: AtomicBool,
+    callback: AtomicWaker,
+}
+
+//Timer and schedule are only accessed under lock
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+//Releases lock on drop, including unwinding from panic.
+//
+//Lock and `is_pending` use `SeqCst`: callback stores `is_pending` then checks lock, while lock holder
+//releases lock then checks `is_pending`, so at least one of them must observe the other's store.
+struct LockGuard<'a>(&'a AtomicBool);
+
+impl<'a> LockGuard<'a> {
+    #[inline]
+    fn try_lock(lock: &'a AtomicBool) -> Option<Self> {
+        match lock.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => Some(Self(lock)),
+            Err(_) => None,
+        }
+    }
+
+    fn lock(lock: &'a AtomicBool) -> Self {
+        loop {
+            match Self::try_lock(lock) {
+                Some(guard) => return guard,
+                None => hint::spin_loop(),
+            }
+        }
+    }
+}
+
+impl<'a> Drop for LockGuard<'a> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl<T: SyncTimer + 'static> Inner<T> {
+    fn with_lock<R, F: FnOnce(&mut T, &mut Schedule) -> R>(self: &Arc<Self>, cb: F) -> R {
+        let result = {
+            let _guard = LockGuard::lock(&self.lock);
+            unsafe {
+                cb(&mut *self.timer.get(), &mut *self.schedule.get())
+            }
+        };
+
+        //Callback could not re-arm timer while lock was held
+        self.rearm_pending();
+        result
+    }
+
+    //Arms timer for next tick.
+    //
+    //Must be called under lock.
+    fn arm(self: &Arc<Self>, timer: &mut T, schedule: &mut Schedule) {
+        schedule.is_registered = true;
+        let waker = task::Waker::from(self.clone());
+        timer.init(|state| state.register(&waker));
+        timer.restart_at(schedule.finish_at);
+    }
+
+    fn rearm_pending(self: &Arc<Self>) {
+        while self.is_pending.load(Ordering::SeqCst) {
+            //Callback may be invoked from signal handler, so it must never wait for lock.
+            //Lock holder re-arms timer on release instead.
+            let _guard = match LockGuard::try_lock(&self.lock) {
+                Some(guard) => guard,
+                None => return,
+            };
+
+            if self.is_pending.swap(false, Ordering::SeqCst) && self.is_running.load(Ordering::Acquire) {
+                let (timer, schedule) = unsafe {
+                    (&mut *self.timer.get(), &mut *self.schedule.get())
+                };
+
+                let now = time::Instant::now();
+                schedule.finish_at = schedule.missed_tick_behavior.next(schedule.finish_at, now, schedule.interval);
+                self.arm(timer, schedule);
+            }
+        }
+    }
+}
+
+impl<T: SyncTimer + 'static> Wake for Inner<T> {
+    #[inline(always)]
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.is_running.load(Ordering::Acquire) {
+            return;
+        }
+
+        self.callback.wake_by_ref();
+        self.is_pending.store(true, Ordering::SeqCst);
+        self.rearm_pending();
+    }
+}
+
+fn noop() {
+}
+
+///Periodic timer that invokes [Callback](../state/trait.Callback.html) without async runtime
+///
+///Timer is re-armed from its own callback context, therefore callback is invoked in the same
+///context as [SyncTimer](timer/trait.SyncTimer.html)'s one (e.g. signal handler for `PosixTimer`
+///without `c_wrapper`).
+///Next tick is scheduled according to [MissedTickBehavior](enum.MissedTickBehavior.html).
+///
+///Each tick is armed as one-shot at absolute deadline, rather than using kernel's periodic timer,
+///so that period and missed tick behavior can be changed while running.
+///
+///## Usage
+///
+///```rust, no_run
+///use async_timer::SyncInterval;
+///
+///use core::sync::atomic::{AtomicUsize, Ordering};
+///use core::time;
+///
+///use std::thread;
+///
+///static TICKS: AtomicUsize = AtomicUsize::new(0);
+///fn on_tick() {
+///    TICKS.fetch_add(1, Ordering::Release);
+///}
+///
+///let mut interval = SyncInterval::platform_new(time::Duration::from_millis(100));
+///interval.register(on_tick as fn());
+///interval.start();
+///
+///thread::sleep(time::Duration::from_millis(550));
+///interval.cancel();
+///assert_eq!(TICKS.load(Ordering::Acquire), 5);
+///```
+pub struct SyncInterval<T: SyncTimer + 'static = SyncPlatformTimer> {
+    inner: Arc<Inner<T>>,
+}
+
+impl SyncInterval {
+    #[inline(always)]
+    ///Creates new instance using sync platform timer
+    pub fn platform_new(interval: time::Duration) -> Self {
+        SyncInterval::<SyncPlatformTimer>::new(interval)
+    }
+}
+
+impl<T: SyncTimer + 'static> SyncInterval<T> {
+    ///Creates new instance with specified timer type.
+    ///
+    ///Interval is not running until [start](struct.SyncInterval.html#method.start) is called.
+    pub fn new(interval: time::Duration) -> Self {
+        assert_time!(interval);
+        Self {
+            inner: Arc::new(Inner {
+                timer: UnsafeCell::new(T::new(interval)),
+                schedule: UnsafeCell::new(Schedule {
+                    interval,
+                    finish_at: time::Instant::now() + interval,
+                    missed_tick_behavior: MissedTickBehavior::Skip,
+                    is_registered: false,
+                }),
+                lock: AtomicBool::new(false),
+                is_pending: AtomicBool::new(false),
+                is_running: AtomicBool::new(false),
+                callback: AtomicWaker::new(),
+            })
+        }
+    }
+
+    #[inline]
+    ///Registers `Callback`, invoked on each tick.
+    ///
+    ///This callback replaces previous one, if any.
+    pub fn register<C: Callback>(&self, cb: C) {
+        cb.register(&self.inner.callback);
+    }
+
+    #[inline(always)]
+    ///Returns whether interval is running.
+    pub fn is_running(&self) -> bool {
+        self.inner.is_running.load(Ordering::Acquire)
+    }
+
+    ///Starts interval, with first tick after full period from now.
+    ///
+    ///If interval is already running, its schedule is reset.
+    pub fn start(&mut self) {
+        let inner = &self.inner;
+        inner.with_lock(|timer, schedule| {
+            inner.is_running.store(true, Ordering::Release);
+            schedule.finish_at = time::Instant::now() + schedule.interval;
+            inner.arm(timer, schedule);
+        })
+    }
+
+    ///Stops interval.
+    pub fn cancel(&mut self) {
+        let inner = &self.inner;
+        inner.with_lock(|timer, _| {
+            inner.is_running.store(false, Ordering::Release);
+            timer.cancel();
+        })
+    }
+
+    #[inline]
+    ///Returns period of the interval.
+    pub fn interval(&self) -> time::Duration {
+        self.inner.with_lock(|_, schedule| schedule.interval)
+    }
+
+    ///Changes period of the interval, which is applied starting from next tick.
+    pub fn set_period(&mut self, interval: time::Duration) {
+        assert_time!(interval);
+        self.inner.with_lock(|_, schedule| schedule.interval = interval)
+    }
+
+    #[inline]
+    ///Returns behavior when tick is missed.
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.inner.with_lock(|_, schedule| schedule.missed_tick_behavior)
+    }
+
+    ///Sets behavior when tick is missed, which is applied starting from next tick.
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.inner.with_lock(|_, schedule| schedule.missed_tick_behavior = behavior)
+    }
+}
+
+impl<T: SyncTimer + 'static> Drop for SyncInterval<T> {
+    fn drop(&mut self) {
+        let inner = &self.inner;
+        inner.with_lock(|timer, schedule| {
+            inner.is_running.store(false, Ordering::Release);
+            if schedule.is_registered {
+                timer.cancel();
+                //Timer's state keeps reference to interval, so it has to be released
+                timer.init(|state| state.register(noop as fn()));
+            }
+        })
+    }
+}
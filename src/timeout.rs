@@ -0,0 +1,160 @@
+//! Owned timeout future
+
+use core::future::Future;
+use core::{fmt, task, time};
+use core::pin::Pin;
+
+use crate::timer::Timer;
+use crate::timer::Platform as PlatformTimer;
+#[cfg(any(unix, windows))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+#[cfg(all(not(any(unix, windows, target_arch = "wasm32")), feature = "custom"))]
+use crate::timer::custom::time::Instant;
+
+#[must_use = "Timeout does nothing unless polled"]
+///Limiter on time to wait for owned `Future`
+///
+///Unlike [Timed](struct.Timed.html) it takes ownership of the future, pinning it internally.
+///Therefore it can be returned from functions or spawned as long as underlying future allows.
+///
+///# Usage
+///
+///```rust, no_run
+///async fn job() {
+///}
+///
+///async fn do_job() {
+///    match async_timer::timeout(job(), core::time::Duration::from_secs(1)).await {
+///        Ok(_) => println!("I'm done!"),
+///        Err(error) => println!("Job failed: {}", error),
+///    }
+///}
+///```
+pub struct Timeout<F, T=PlatformTimer> {
+    fut: F,
+    timer: T,
+    timeout: time::Duration,
+    #[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+    //Timer starts on first poll, so it is armed with time remaining until deadline at that moment.
+    deadline: Option<Instant>,
+}
+
+impl<F: Future> Timeout<F> {
+    #[inline]
+    ///Creates new instance using [Platform](timer/type.Platform.html) timer.
+    pub fn platform_new(fut: F, timeout: time::Duration) -> Self {
+        Self::new(fut, timeout)
+    }
+}
+
+impl<F: Future, T: Timer> Timeout<F, T> {
+    ///Creates new instance with specified timeout
+    ///
+    ///Requires to specify `Timer` type (e.g. `Timeout::<_, timer::Platform>::new()`)
+    pub fn new(fut: F, timeout: time::Duration) -> Self {
+        Self {
+            fut,
+            timer: T::new(timeout),
+            timeout,
+            #[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+            deadline: None,
+        }
+    }
+
+    #[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+    ///Creates new instance, which expires at `deadline`
+    ///
+    ///Requires to specify `Timer` type (e.g. `Timeout::<_, timer::Platform>::new_at()`)
+    pub fn new_at(fut: F, deadline: Instant) -> Self {
+        let timeout = crate::ext::until(deadline);
+        Self {
+            fut,
+            timer: T::new(timeout),
+            timeout,
+            deadline: Some(deadline),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns reference to underlying future.
+    pub fn get_ref(&self) -> &F {
+        &self.fut
+    }
+
+    #[inline(always)]
+    ///Returns timeout of the future.
+    pub fn timeout(&self) -> time::Duration {
+        self.timeout
+    }
+
+    #[inline(always)]
+    ///Consumes self, returning underlying future.
+    pub fn into_inner(self) -> F {
+        self.fut
+    }
+}
+
+impl<F: Future, T: Timer> Future for Timeout<F, T> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        //`fut` is pinned structurally, while `Timer` is `Unpin`
+        let this = unsafe {
+            self.get_unchecked_mut()
+        };
+
+        let fut = unsafe {
+            Pin::new_unchecked(&mut this.fut)
+        };
+
+        match Future::poll(fut, ctx) {
+            task::Poll::Pending => (),
+            task::Poll::Ready(result) => return task::Poll::Ready(Ok(result)),
+        }
+
+        #[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+        if let Some(deadline) = this.deadline.take() {
+            if !this.timer.is_ticking() && !this.timer.is_expired() {
+                this.timeout = crate::ext::until(deadline);
+                this.timer.restart(this.timeout);
+            }
+        }
+
+        match Future::poll(Pin::new(&mut this.timer), ctx) {
+            task::Poll::Pending => task::Poll::Pending,
+            task::Poll::Ready(_) => task::Poll::Ready(Err(Elapsed(this.timeout))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+///Error when [Timeout](struct.Timeout.html) expires
+pub struct Elapsed(time::Duration);
+
+impl Elapsed {
+    #[inline(always)]
+    ///Returns timeout, which has elapsed.
+    pub fn timeout(&self) -> time::Duration {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::std::error::Error for Elapsed {}
+
+impl fmt::Debug for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_secs() {
+            0 => write!(f, "Future expired in {} ms", self.0.as_millis()),
+            secs => write!(f, "Future expired in {} seconds and {} ms", secs, self.0.subsec_millis()),
+        }
+    }
+}
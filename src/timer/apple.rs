@@ -237,3 +237,11 @@ impl Future for AppleTimer {
         crate::timer::poll_sync(self.get_mut(), ctx)
     }
 }
+
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for AppleTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
@@ -0,0 +1,154 @@
+use async_io_2 as async_io;
+
+use async_io::Async;
+
+use core::{task, time};
+use core::pin::Pin;
+use core::future::Future;
+
+use super::fd::{TimerFd, RawTimer};
+
+enum State<T> {
+    Init(time::Duration),
+    Running(T, bool),
+}
+
+///Timer implemented on top of `async_io::Async`
+///
+///Works with any runtime that is driven by `async-io` reactor (e.g. `smol` or `async-std`).
+pub struct AsyncIoTimer<T: TimerFd = RawTimer> {
+    state: State<Async<T>>
+}
+
+impl AsyncIoTimer<RawTimer> {
+    #[inline]
+    ///Creates new instance
+    pub const fn new(time: time::Duration) -> Self {
+        Self {
+            state: State::Init(time),
+        }
+    }
+}
+
+#[inline(always)]
+fn get_fd<T: TimerFd>(fd: &mut Async<T>) -> &mut T {
+    //Safety: fd is only re-armed, never replaced
+    unsafe {
+        fd.get_mut()
+    }
+}
+
+impl<T: TimerFd> super::Timer for AsyncIoTimer<T> {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        debug_assert!(timeout.as_millis() <= u32::max_value().into());
+        Self {
+            state: State::Init(timeout),
+        }
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, state) => !*state,
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, state) => *state
+        }
+    }
+
+    fn restart(&mut self, new_value: time::Duration) {
+        assert_time!(new_value);
+        debug_assert!(new_value.as_millis() <= u32::max_value().into());
+
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = new_value;
+            },
+            State::Running(ref mut fd, ref mut state) => {
+                *state = false;
+                get_fd(fd).set(new_value);
+            }
+        }
+    }
+
+    fn restart_at(&mut self, deadline: crate::std::time::Instant) {
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = crate::ext::until(deadline);
+            },
+            State::Running(ref mut fd, ref mut state) => {
+                *state = false;
+                get_fd(fd).set_at(deadline);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn restart_ctx(&mut self, new_value: time::Duration, _: &task::Waker) {
+        self.restart(new_value)
+    }
+
+    fn cancel(&mut self) {
+        match &mut self.state {
+            State::Init(_) => (),
+            State::Running(ref mut fd, ref mut state) => {
+                *state = true;
+                get_fd(fd).unset();
+            }
+        }
+    }
+}
+
+impl<T: TimerFd> Future for AsyncIoTimer<T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        if let State::Init(ref timeout) = &self.state {
+            let mut fd = Async::new(T::new()).expect("To create Async fd");
+            get_fd(&mut fd).set(*timeout);
+            self.state = State::Running(fd, false)
+        };
+
+        if let State::Running(ref mut fd, ref mut state) = &mut self.state {
+            if *state {
+                return task::Poll::Ready(());
+            }
+
+            loop {
+                match fd.poll_readable(ctx) {
+                    task::Poll::Pending => return task::Poll::Pending,
+                    task::Poll::Ready(ready) => {
+                        ready.expect("Unable to read async timer's fd");
+
+                        match get_fd(fd).read() {
+                            //Spurious wake up, poll again to register interest
+                            0 => continue,
+                            _ => {
+                                *state = true;
+                                return task::Poll::Ready(())
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            unreach!();
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: TimerFd> futures_core_03::future::FusedFuture for AsyncIoTimer<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
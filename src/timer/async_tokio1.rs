@@ -1,205 +1,155 @@
 use tokio_1 as tokio;
 
 use tokio::io::unix::AsyncFd;
-use libc::c_int;
 
 use core::{task, time};
 use core::pin::Pin;
 use core::future::Future;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::std::panic;
+use crate::std::sync::{Arc, Mutex};
+use crate::alloc::vec::Vec;
+
+use super::fd::{TimerFd, RawTimer};
+use super::SyncPlatform;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///[TimerPool](struct.TimerPool.html) statistics
+pub struct PoolStats {
+    ///Number of timers that re-used pooled fd.
+    pub hits: usize,
+    ///Number of timers that had to create new fd.
+    pub misses: usize,
+    ///Number of fds currently kept in pool.
+    pub idle: usize,
+}
 
-pub trait TimerFd: crate::std::os::unix::io::AsRawFd + Sync + Send + Unpin {
-    fn new() -> Self;
-    fn set(&mut self, time: time::Duration);
-    fn unset(&mut self);
-    fn read(&mut self) -> usize;
+struct PoolInner<T: TimerFd> {
+    //Fds together with runtime, which reactor they are registered with
+    fds: Mutex<Vec<(AsyncFd<T>, tokio::runtime::Id)>>,
+    max_idle: usize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
 }
 
-///Wrapper over fd based timer.
-pub struct RawTimer(c_int);
+///Pool of timer's fds together with their `AsyncFd` registrations.
+///
+///Dropped timers created by pool return their disarmed fd to the pool, so that next timer
+///can avoid creating new fd and registering it with reactor.
+///
+///As registration is bound to runtime's reactor, fd is re-used only within the runtime that
+///registered it, while fds of other runtimes are discarded.
+pub struct TimerPool<T: TimerFd = RawTimer>(Arc<PoolInner<T>>);
 
-impl crate::std::os::unix::io::AsRawFd for RawTimer {
+impl<T: TimerFd> Clone for TimerPool<T> {
     #[inline(always)]
-    fn as_raw_fd(&self) -> c_int {
-        self.0
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
     }
 }
 
-impl Drop for RawTimer {
-    #[inline(always)]
-    fn drop(&mut self) {
-        unsafe {
-            libc::close(self.0);
-        }
+impl TimerPool<RawTimer> {
+    #[inline]
+    ///Creates new pool that keeps up to `max_idle` unused fds.
+    pub fn new(max_idle: usize) -> Self {
+        Self::with_max_idle(max_idle)
     }
 }
 
-#[cfg(target_os = "android")]
-mod sys {
-    #[repr(C)]
-    pub struct itimerspec {
-        pub it_interval: libc::timespec,
-        pub it_value: libc::timespec,
+impl<T: TimerFd> TimerPool<T> {
+    ///Creates new pool that keeps up to `max_idle` unused fds of specified type.
+    pub fn with_max_idle(max_idle: usize) -> Self {
+        Self(Arc::new(PoolInner {
+            fds: Mutex::new(Vec::new()),
+            max_idle,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }))
     }
 
-    extern "C" {
-        pub fn timerfd_create(clockid: libc::clockid_t, flags: libc::c_int) -> libc::c_int;
-        pub fn timerfd_settime(timerid: libc::c_int, flags: libc::c_int, new_value: *const itimerspec, old_value: *mut itimerspec) -> libc::c_int;
+    #[inline]
+    ///Creates new timer that uses pool to acquire its fd.
+    pub fn timer(&self, timeout: time::Duration) -> AsyncTokioTimer<T> {
+        assert_time!(timeout);
+        AsyncTokioTimer {
+            state: State::Init(timeout),
+            pool: Some(self.clone()),
+        }
     }
 
-    pub const TFD_NONBLOCK: libc::c_int = libc::O_NONBLOCK;
-}
-
-#[cfg(target_os = "linux")]
-use libc as sys;
-
-#[cfg(any(target_os = "linux", target_os = "android"))]
-impl TimerFd for RawTimer {
-    fn new() -> Self {
-        let fd = unsafe { sys::timerfd_create(libc::CLOCK_MONOTONIC, sys::TFD_NONBLOCK) };
-
-        os_assert!(fd != -1);
-        Self(fd)
+    ///Returns pool statistics.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            hits: self.0.hits.load(Ordering::Relaxed),
+            misses: self.0.misses.load(Ordering::Relaxed),
+            idle: self.0.fds.lock().unwrap_or_else(|error| error.into_inner()).len(),
+        }
     }
 
-    fn set(&mut self, timeout: time::Duration) {
-        #[cfg(not(target_pointer_width = "64"))]
-        use core::convert::TryFrom;
-
-        let it_value = libc::timespec {
-            tv_sec: timeout.as_secs() as libc::time_t,
-            #[cfg(target_pointer_width = "64")]
-            tv_nsec: libc::suseconds_t::from(timeout.subsec_nanos()),
-            #[cfg(not(target_pointer_width = "64"))]
-            tv_nsec: libc::suseconds_t::try_from(timeout.subsec_nanos()).unwrap_or(libc::suseconds_t::max_value()),
+    fn get(&self, runtime: tokio::runtime::Id) -> Option<AsyncFd<T>> {
+        let fd = {
+            let mut fds = self.0.fds.lock().unwrap_or_else(|error| error.into_inner());
+            //Registration with other runtime's reactor is unusable, which may be even shut down already
+            fds.retain(|(_, fd_runtime)| *fd_runtime == runtime);
+            fds.pop().map(|(fd, _)| fd)
         };
-
-        let timer = sys::itimerspec {
-            it_interval: unsafe { core::mem::MaybeUninit::zeroed().assume_init() },
-            it_value,
+        match fd.is_some() {
+            true => self.0.hits.fetch_add(1, Ordering::Relaxed),
+            false => self.0.misses.fetch_add(1, Ordering::Relaxed),
         };
-
-        let ret = unsafe { sys::timerfd_settime(self.0, 0, &timer, core::ptr::null_mut()) };
-        os_assert!(ret != -1);
-    }
-
-    #[inline]
-    fn unset(&mut self) {
-        self.set(time::Duration::from_secs(0));
+        fd
     }
 
-    fn read(&mut self) -> usize {
-        let mut read_num = 0u64;
-        match unsafe { libc::read(self.0, &mut read_num as *mut u64 as *mut _, 8) } {
-            -1 => {
-                let error = crate::std::io::Error::last_os_error();
-                match error.kind() {
-                    crate::std::io::ErrorKind::WouldBlock => 0,
-                    _ => panic!("Unexpected read error: {}", error),
-                }
-            }
-            _ => read_num as usize,
+    fn put(&self, mut fd: AsyncFd<T>, runtime: tokio::runtime::Id) {
+        let mut fds = self.0.fds.lock().unwrap_or_else(|error| error.into_inner());
+        if fds.len() < self.0.max_idle {
+            fd.get_mut().unset();
+            //Make sure that next user doesn't observe previous expiration
+            fd.get_mut().read();
+            fds.push((fd, runtime));
         }
     }
 }
 
-#[cfg(any(target_os = "bitrig", target_os = "dragonfly", target_os = "freebsd", target_os = "ios", target_os = "macos", target_os = "netbsd", target_os = "openbsd"))]
-impl TimerFd for RawTimer {
-    fn new() -> Self {
-        let fd = unsafe {
-            libc::kqueue()
-        };
+//Registers fd with reactor of current runtime, returning `None` if it has no IO driver.
+fn register<T: TimerFd>(runtime: tokio::runtime::Id) -> Option<AsyncFd<T>> {
+    //Runtimes without IO driver, which are remembered to probe each runtime only once
+    static NO_IO: Mutex<Vec<tokio::runtime::Id>> = Mutex::new(Vec::new());
 
-        //If you hit this, then most likely you run into OS imposed limit on file descriptor number
-        os_assert!(fd != -1);
-        Self(fd)
+    if NO_IO.lock().unwrap_or_else(|error| error.into_inner()).contains(&runtime) {
+        return None;
     }
 
-    fn set(&mut self, time: time::Duration) {
-        let timeout = libc::timespec {
-            tv_sec: 0,
-            tv_nsec: 0,
-        };
-        let mut empty = [];
-        let mut event = libc::kevent {
-            ident: 1,
-            filter: libc::EVFILT_TIMER,
-            flags: libc::EV_ADD | libc::EV_ENABLE | libc::EV_ONESHOT,
-            fflags: libc::NOTE_NSECONDS,
-            data: 0,
-            udata: core::ptr::null_mut(),
-        };
-
-        let mut time = time.as_nanos();
-        if time > isize::max_value() as u128 {
-            event.fflags = libc::NOTE_USECONDS;
-            time /= 1_000;
-        }
-        if time > isize::max_value() as u128 {
-            event.fflags = 0; //default value is ms
-            time /= 1_000;
+    //Tokio panics instead of returning error when IO driver is disabled.
+    #[allow(deprecated)]
+    match panic::catch_unwind(panic::AssertUnwindSafe(|| AsyncFd::with_interest(T::new(), tokio::io::Interest::READABLE))) {
+        Ok(fd) => fd.ok(),
+        Err(_) => {
+            NO_IO.lock().unwrap_or_else(|error| error.into_inner()).push(runtime);
+            None
         }
-        if time > isize::max_value() as u128 {
-            event.fflags = libc::NOTE_SECONDS;
-            time /= 1_000;
-        }
-
-        event.data = time as _;
-        let set = unsafe {
-            libc::kevent(self.0, &event, 1, empty.as_mut_ptr(), 0, &timeout)
-        };
-        os_assert!(set != -1);
-    }
-
-    fn unset(&mut self) {
-        let timeout = libc::timespec {
-            tv_sec: 0,
-            tv_nsec: 0,
-        };
-        let mut empty = [];
-        let event = libc::kevent {
-            ident: 1,
-            filter: libc::EVFILT_TIMER,
-            flags: libc::EV_DELETE,
-            fflags: 0,
-            data: 0,
-            udata: core::ptr::null_mut(),
-        };
-        let unset = unsafe {
-            libc::kevent(self.0, &event, 1, empty.as_mut_ptr(), 0, &timeout)
-        };
-        os_assert!(unset != -1);
-    }
-
-    fn read(&mut self) -> usize {
-        let timeout = libc::timespec {
-            tv_sec: 0,
-            tv_nsec: 0,
-        };
-        let empty = [];
-        let mut event = libc::kevent {
-            ident: 0,
-            filter: libc::EVFILT_TIMER,
-            flags: 0,
-            fflags: 0,
-            data: 0,
-            udata: core::ptr::null_mut(),
-        };
-        let read = unsafe {
-            libc::kevent(self.0, empty.as_ptr(), 0, &mut event, 1, &timeout)
-        };
-        os_assert!(read != -1);
-        read as _
     }
 }
 
 enum State<T> {
     Init(time::Duration),
-    Running(T, bool),
+    //Fd, whether it is expired and runtime it is registered with
+    Running(T, bool, tokio::runtime::Id),
+    //No tokio reactor is available
+    Fallback(SyncPlatform),
 }
 
 ///Timer implemented on top of `AsyncFd`
+///
+///When polled outside of tokio runtime with enabled IO driver, it falls back to
+///[SyncPlatform](type.SyncPlatform.html) timer.
+///
+///Runtime without IO driver is detected by catching tokio's panic on fd registration, which is
+///reported by panic hook once per runtime and requires `panic = "unwind"`.
 pub struct AsyncTokioTimer<T: TimerFd> {
-    state: State<AsyncFd<T>>
+    state: State<AsyncFd<T>>,
+    pool: Option<TimerPool<T>>,
 }
 
 impl AsyncTokioTimer<RawTimer> {
@@ -208,6 +158,7 @@ impl AsyncTokioTimer<RawTimer> {
     pub const fn new(time: time::Duration) -> Self {
         Self {
             state: State::Init(time),
+            pool: None,
         }
     }
 }
@@ -219,6 +170,7 @@ impl<T: TimerFd> super::Timer for AsyncTokioTimer<T> {
         debug_assert!(timeout.as_millis() <= u32::max_value().into());
         Self {
             state: State::Init(timeout),
+            pool: None,
         }
     }
 
@@ -226,7 +178,8 @@ impl<T: TimerFd> super::Timer for AsyncTokioTimer<T> {
     fn is_ticking(&self) -> bool {
         match &self.state {
             State::Init(_) => false,
-            State::Running(_, state) => !*state,
+            State::Running(_, state, _) => !*state,
+            State::Fallback(timer) => timer.is_ticking(),
         }
     }
 
@@ -234,7 +187,8 @@ impl<T: TimerFd> super::Timer for AsyncTokioTimer<T> {
     fn is_expired(&self) -> bool {
         match &self.state {
             State::Init(_) => false,
-            State::Running(_, state) => *state
+            State::Running(_, state, _) => *state,
+            State::Fallback(timer) => timer.is_expired(),
         }
     }
 
@@ -246,20 +200,44 @@ impl<T: TimerFd> super::Timer for AsyncTokioTimer<T> {
             State::Init(ref mut timeout) => {
                 *timeout = new_value;
             },
-            State::Running(ref mut fd, ref mut state) => {
+            State::Running(ref mut fd, ref mut state, _) => {
                 *state = false;
                 fd.get_mut().set(new_value);
-            }
+            },
+            State::Fallback(ref mut timer) => timer.restart(new_value),
         }
     }
 
-    #[inline(always)]
-    fn restart_ctx(&mut self, new_value: time::Duration, _: &task::Waker) {
-        self.restart(new_value)
+    fn restart_at(&mut self, deadline: crate::std::time::Instant) {
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = crate::ext::until(deadline);
+            },
+            State::Running(ref mut fd, ref mut state, _) => {
+                *state = false;
+                fd.get_mut().set_at(deadline);
+            },
+            State::Fallback(ref mut timer) => timer.restart_at(deadline),
+        }
+    }
+
+    #[inline]
+    fn restart_ctx(&mut self, new_value: time::Duration, waker: &task::Waker) {
+        match &mut self.state {
+            State::Fallback(ref mut timer) => timer.restart_ctx(new_value, waker),
+            _ => self.restart(new_value),
+        }
     }
 
     fn cancel(&mut self) {
-        unreachable!();
+        match &mut self.state {
+            State::Init(_) => (),
+            State::Running(ref mut fd, ref mut state, _) => {
+                *state = true;
+                fd.get_mut().unset();
+            },
+            State::Fallback(ref mut timer) => timer.cancel(),
+        }
     }
 }
 
@@ -267,33 +245,50 @@ impl<T: TimerFd> Future for AsyncTokioTimer<T> {
     type Output = ();
 
     fn poll(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
-        if let State::Init(ref timeout) = &self.state {
-            let mut fd = AsyncFd::with_interest(T::new(), tokio::io::Interest::READABLE).expect("To create AsyncFd");
-            fd.get_mut().set(*timeout);
-            self.state = State::Running(fd, false)
+        if let State::Init(timeout) = self.state {
+            self.state = match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    let runtime = handle.id();
+                    let fd = match self.pool.as_ref().and_then(|pool| pool.get(runtime)) {
+                        Some(fd) => Some(fd),
+                        None => register(runtime),
+                    };
+
+                    match fd {
+                        Some(mut fd) => {
+                            fd.get_mut().set(timeout);
+                            State::Running(fd, false, runtime)
+                        },
+                        None => State::Fallback(SyncPlatform::new(timeout)),
+                    }
+                },
+                Err(_) => State::Fallback(SyncPlatform::new(timeout)),
+            };
         };
 
-        if let State::Running(ref mut fd, ref mut state) = &mut self.state {
+        if let State::Fallback(ref mut timer) = &mut self.state {
+            return Future::poll(Pin::new(timer), ctx);
+        }
+
+        if let State::Running(ref mut fd, ref mut state, _) = &mut self.state {
             if *state {
                 return task::Poll::Ready(());
             }
 
-            let fd = Pin::new(fd);
-            match fd.poll_read_ready(ctx) {
-                task::Poll::Pending => return task::Poll::Pending,
-                task::Poll::Ready(ready) => {
-                    let mut ready = ready.expect("Unable to read async timer's fd");
-                    //technically we should read first, but we cannot borrow as mut then
-                    ready.clear_ready();
-
-                    match fd.get_mut().get_mut().read() {
-                        0 => {
-                            *state = false;
-                            return task::Poll::Pending
-                        },
-                        _ => {
-                            *state = true;
-                            return task::Poll::Ready(())
+            loop {
+                match fd.poll_read_ready(ctx) {
+                    task::Poll::Pending => return task::Poll::Pending,
+                    task::Poll::Ready(ready) => {
+                        //technically we should read first, but we cannot borrow as mut then
+                        ready.expect("Unable to read async timer's fd").clear_ready();
+
+                        match fd.get_mut().read() {
+                            //Stale readiness (e.g. of pooled fd), poll again to register interest
+                            0 => continue,
+                            _ => {
+                                *state = true;
+                                return task::Poll::Ready(())
+                            }
                         }
                     }
                 }
@@ -304,5 +299,23 @@ impl<T: TimerFd> Future for AsyncTokioTimer<T> {
     }
 }
 
+#[cfg(feature = "stream")]
+impl<T: TimerFd> futures_core_03::future::FusedFuture for AsyncTokioTimer<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
+impl<T: TimerFd> Drop for AsyncTokioTimer<T> {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            if let State::Running(fd, _, runtime) = core::mem::replace(&mut self.state, State::Init(time::Duration::from_secs(0))) {
+                pool.put(fd, runtime);
+            }
+        }
+    }
+}
+
 ///Timer based on tokio's `AsyncFd`
 pub type AsyncTimer = AsyncTokioTimer<RawTimer>;
@@ -0,0 +1,208 @@
+//! Timer backend interface
+
+use core::{task, time};
+use core::pin::Pin;
+use core::future::Future;
+
+use crate::state::TimerState;
+use crate::alloc::sync::Arc;
+
+///Interface to implement custom timer backend
+///
+///Backend is only responsible for arming and disarming of underlying timer, while
+///[BackendTimer](struct.BackendTimer.html) implements `Timer`, `SyncTimer` and `Future` on top of it.
+///
+///On expiration backend must call [TimerState::wake](../state/struct.TimerState.html#method.wake)
+///of the state, provided on creation.
+///
+///State is reset on restart before timer is armed again, hence expiration of previous arming,
+///which cannot be stopped by `disarm`, would expire new one.
+///Such backend should obtain [TimerState::generation](../state/struct.TimerState.html#method.generation)
+///in `arm` and notify via [TimerState::wake_generation](../state/struct.TimerState.html#method.wake_generation).
+///
+///## Usage
+///
+///```
+///use async_timer::timer::{TimerBackend, BackendTimer, SyncTimer};
+///use async_timer::state::TimerState;
+///
+///use core::time;
+///
+///use std::sync::Arc;
+///use std::thread;
+///
+///struct ThreadBackend {
+///    state: Arc<TimerState>,
+///}
+///
+///impl TimerBackend for ThreadBackend {
+///    fn create(state: Arc<TimerState>) -> Self {
+///        Self {
+///            state,
+///        }
+///    }
+///
+///    fn arm(&mut self, timeout: time::Duration) {
+///        let state = self.state.clone();
+///        //Thread cannot be stopped, so its notification is discarded once timer is restarted
+///        let generation = state.generation();
+///        thread::spawn(move || {
+///            thread::sleep(timeout);
+///            state.wake_generation(generation);
+///        });
+///    }
+///
+///    fn disarm(&mut self) {
+///        //Late notification has no effect after cancel or restart, due to state's generation
+///    }
+///}
+///
+///let mut timer = BackendTimer::<ThreadBackend>::new(time::Duration::from_millis(10));
+///timer.wait_blocking();
+///```
+pub trait TimerBackend: Send + Sync + Unpin + Sized {
+    ///Creates underlying timer, which is to notify provided state.
+    ///
+    ///Timer must not be armed until `arm` is called.
+    fn create(state: Arc<TimerState>) -> Self;
+
+    ///Arms timer to expire after `timeout`, replacing previous timeout, if any.
+    fn arm(&mut self, timeout: time::Duration);
+
+    ///Disarms timer, if it is armed.
+    ///
+    ///Called on cancellation, restart and drop, therefore it should be no-op if timer is not armed.
+    ///
+    ///On restart, state is reset after `disarm`, therefore notification of previous arming must be
+    ///prevented by `disarm` or discarded via state's generation.
+    fn disarm(&mut self);
+}
+
+enum State<B> {
+    Init(time::Duration),
+    Running(B, Arc<TimerState>),
+}
+
+///Timer implemented on top of [TimerBackend](trait.TimerBackend.html)
+pub struct BackendTimer<B: TimerBackend> {
+    state: State<B>,
+}
+
+impl<B: TimerBackend> BackendTimer<B> {
+    #[inline]
+    ///Creates new instance
+    pub const fn new(time: time::Duration) -> Self {
+        Self {
+            state: State::Init(time),
+        }
+    }
+
+    #[inline]
+    ///Returns reference to backend, if timer is started.
+    pub fn backend(&self) -> Option<&B> {
+        match &self.state {
+            State::Init(_) => None,
+            State::Running(ref backend, _) => Some(backend),
+        }
+    }
+}
+
+impl<B: TimerBackend> super::Timer for BackendTimer<B> {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        Self::new(timeout)
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, ref state) => !state.is_done(),
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, ref state) => state.is_done(),
+        }
+    }
+
+    fn restart(&mut self, new_value: time::Duration) {
+        assert_time!(new_value);
+
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = new_value;
+            },
+            State::Running(ref mut backend, ref state) => {
+                backend.disarm();
+                state.reset();
+                backend.arm(new_value);
+            }
+        }
+    }
+
+    fn restart_ctx(&mut self, new_value: time::Duration, waker: &task::Waker) {
+        if let State::Running(_, ref state) = self.state {
+            state.register(waker);
+        }
+        self.restart(new_value)
+    }
+
+    fn cancel(&mut self) {
+        match &mut self.state {
+            State::Init(_) => (),
+            State::Running(ref mut backend, ref state) => {
+                state.cancel();
+                backend.disarm();
+            }
+        }
+    }
+}
+
+impl<B: TimerBackend> super::SyncTimer for BackendTimer<B> {
+    fn init<R, F: Fn(&TimerState) -> R>(&mut self, init: F) -> R {
+        if let State::Init(timeout) = self.state {
+            let state = Arc::new(TimerState::new());
+            init(&state);
+
+            let mut backend = B::create(state.clone());
+            backend.arm(timeout);
+
+            self.state = State::Running(backend, state);
+        }
+
+        match &self.state {
+            State::Running(_, ref state) => init(state),
+            State::Init(_) => unreach!(),
+        }
+    }
+}
+
+impl<B: TimerBackend> Future for BackendTimer<B> {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        super::poll_sync(self.get_mut(), ctx)
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<B: TimerBackend> futures_core_03::future::FusedFuture for BackendTimer<B> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
+impl<B: TimerBackend> Drop for BackendTimer<B> {
+    fn drop(&mut self) {
+        if let State::Running(ref mut backend, _) = self.state {
+            backend.disarm();
+        }
+    }
+}
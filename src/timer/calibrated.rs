@@ -0,0 +1,265 @@
+//! Latency calibration
+
+#[cfg(any(unix, windows))]
+use std::time;
+#[cfg(target_arch = "wasm32")]
+use web_time as time;
+#[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+use crate::timer::custom::time;
+use core::{cmp, task};
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use super::Timer;
+use super::Platform as PlatformTimer;
+
+//No sample recorded yet
+const UNSET: usize = usize::MAX;
+//Weight of new sample is `1 / 2^SMOOTHING`
+const SMOOTHING: u32 = 3;
+//Limits estimate in order to fit `usize` on 32bit targets and to ignore outliers, like suspended process.
+const MAX_LATENCY_NANOS: usize = 1_000_000_000;
+
+///Estimates typical wakeup latency of timer
+///
+///Latency is time between requested deadline and actual expiration, which is smoothed using
+///exponential moving average of recorded samples.
+///
+///Calibrator is lock-free and can be placed in `static` in order to be shared by timers of the same type,
+///as latency depends on particular timer implementation.
+pub struct Calibrator {
+    latency: AtomicUsize,
+}
+
+impl Calibrator {
+    #[inline]
+    ///Creates new instance without any estimate.
+    pub const fn new() -> Self {
+        Self {
+            latency: AtomicUsize::new(UNSET),
+        }
+    }
+
+    #[inline]
+    ///Returns calibrator used by [CalibratedTimer](struct.CalibratedTimer.html) by default.
+    pub fn global() -> &'static Self {
+        static GLOBAL: Calibrator = Calibrator::new();
+        &GLOBAL
+    }
+
+    #[inline]
+    ///Returns current latency estimate, which is zero until first sample is recorded.
+    pub fn latency(&self) -> time::Duration {
+        match self.latency.load(Ordering::Relaxed) {
+            UNSET => time::Duration::from_secs(0),
+            nanos => time::Duration::from_nanos(nanos as u64),
+        }
+    }
+
+    ///Records time by which timer expired later than it was armed for.
+    pub fn record(&self, latency: time::Duration) {
+        let sample = cmp::min(latency.as_nanos(), MAX_LATENCY_NANOS as u128) as usize;
+
+        let _ = self.latency.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |estimate| match estimate {
+            UNSET => Some(sample),
+            estimate => Some(estimate - (estimate >> SMOOTHING) + (sample >> SMOOTHING)),
+        });
+    }
+
+    #[inline]
+    ///Discards current estimate.
+    pub fn reset(&self) {
+        self.latency.store(UNSET, Ordering::Relaxed);
+    }
+
+    ///Measures latency of timer `T` by awaiting `samples` timers with specified `timeout`.
+    ///
+    ///Intended to be used at startup, before relying on [CalibratedTimer](struct.CalibratedTimer.html).
+    pub async fn calibrate<T: Timer>(&self, samples: usize, timeout: time::Duration) {
+        let mut timer = T::new(timeout);
+
+        for idx in 0..samples {
+            if idx > 0 {
+                timer.restart(timeout);
+            }
+
+            let start = time::Instant::now();
+            (&mut timer).await;
+            self.record(start.elapsed().saturating_sub(timeout));
+        }
+    }
+}
+
+impl Default for Calibrator {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Timer that is armed earlier by latency estimated by [Calibrator](struct.Calibrator.html)
+///
+///Each expiration is recorded by calibrator, therefore estimate is continuously adjusted, keeping
+///average expiration centred on requested deadline.
+///As result, some expirations happen before deadline.
+///
+///## Usage
+///
+///```rust, no_run
+///use async_timer::timer::{Calibrator, CalibratedTimer, Platform};
+///
+///use core::time;
+///
+///static CALIBRATOR: Calibrator = Calibrator::new();
+///
+///async fn job() {
+///    CALIBRATOR.calibrate::<Platform>(10, time::Duration::from_millis(10)).await;
+///
+///    let mut timer = CalibratedTimer::platform_new(&CALIBRATOR, time::Duration::from_millis(100));
+///    (&mut timer).await;
+///}
+///```
+pub struct CalibratedTimer<T=PlatformTimer> {
+    timer: Option<T>,
+    calibrator: &'static Calibrator,
+    timeout: time::Duration,
+    //Deadline for which underlying timer is armed.
+    armed_deadline: Option<time::Instant>,
+    deadline: Option<time::Instant>,
+    expired: bool,
+}
+
+impl CalibratedTimer {
+    #[inline(always)]
+    ///Creates new instance using platform timer
+    pub fn platform_new(calibrator: &'static Calibrator, timeout: time::Duration) -> Self {
+        CalibratedTimer::<PlatformTimer>::with_calibrator(calibrator, timeout)
+    }
+}
+
+impl<T: Timer> CalibratedTimer<T> {
+    ///Creates new instance with specified calibrator.
+    pub fn with_calibrator(calibrator: &'static Calibrator, timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        Self {
+            timer: None,
+            calibrator,
+            timeout,
+            armed_deadline: None,
+            deadline: None,
+            expired: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns calibrator used by timer.
+    pub fn calibrator(&self) -> &'static Calibrator {
+        self.calibrator
+    }
+
+    #[inline(always)]
+    ///Returns requested deadline of the timer, if it is started.
+    pub fn deadline(&self) -> Option<time::Instant> {
+        self.deadline
+    }
+
+    fn arm(&mut self, waker: Option<&task::Waker>) {
+        let now = time::Instant::now();
+        //Timer cannot be armed with zero timeout
+        let timeout = cmp::max(self.timeout.saturating_sub(self.calibrator.latency()), time::Duration::from_nanos(1));
+
+        self.deadline = Some(now + self.timeout);
+        self.armed_deadline = Some(now + timeout);
+        self.expired = false;
+
+        match (self.timer.as_mut(), waker) {
+            (Some(timer), Some(waker)) => timer.restart_ctx(timeout, waker),
+            (Some(timer), None) => timer.restart(timeout),
+            (None, _) => self.timer = Some(T::new(timeout)),
+        }
+    }
+}
+
+impl<T: Timer> Timer for CalibratedTimer<T> {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        Self::with_calibrator(Calibrator::global(), timeout)
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        self.deadline.is_some() && !self.expired
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    fn restart(&mut self, timeout: time::Duration) {
+        assert_time!(timeout);
+
+        self.timeout = timeout;
+        if self.deadline.is_some() {
+            self.arm(None);
+        }
+    }
+
+    fn restart_ctx(&mut self, timeout: time::Duration, waker: &task::Waker) {
+        assert_time!(timeout);
+
+        self.timeout = timeout;
+        if self.deadline.is_some() {
+            self.arm(Some(waker));
+        }
+    }
+
+    fn cancel(&mut self) {
+        if let Some(timer) = self.timer.as_mut() {
+            //Underlying timer is no longer armed once expired
+            if !self.expired {
+                timer.cancel();
+            }
+        }
+        if self.deadline.is_some() {
+            self.expired = true;
+        }
+    }
+}
+
+impl<T: Timer> Future for CalibratedTimer<T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.expired {
+            return task::Poll::Ready(());
+        }
+
+        if this.deadline.is_none() {
+            this.arm(None);
+        }
+
+        match (this.timer.as_mut(), this.armed_deadline) {
+            (Some(timer), Some(armed_deadline)) => match Future::poll(Pin::new(timer), ctx) {
+                task::Poll::Pending => task::Poll::Pending,
+                task::Poll::Ready(()) => {
+                    this.calibrator.record(time::Instant::now().saturating_duration_since(armed_deadline));
+                    this.expired = true;
+                    task::Poll::Ready(())
+                },
+            },
+            _ => unreach!(),
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: Timer> futures_core_03::future::FusedFuture for CalibratedTimer<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        Timer::is_expired(self)
+    }
+}
@@ -0,0 +1,366 @@
+//! Custom timer driven by application's hooks
+//!
+//! Intended for targets without OS, where application provides monotonic counter and single
+//! alarm (e.g. hardware timer's compare interrupt) that is shared by all timers.
+//!
+//! Synchronization relies on `critical-section`, therefore application must provide its implementation.
+//!
+//! ## Usage
+//!
+//!```no_run
+//!use async_timer::timer::custom::{self, Hooks};
+//!
+//!fn now() -> u64 {
+//!    //Read hardware counter
+//!    0
+//!}
+//!
+//!fn arm(_tick: u64) {
+//!    //Set hardware compare register to `_tick`
+//!}
+//!
+//!//Hardware compare interrupt handler
+//!fn on_compare_interrupt() {
+//!    custom::on_alarm();
+//!}
+//!
+//!custom::init(Hooks {
+//!    frequency: 1_000_000,
+//!    now,
+//!    arm,
+//!});
+//!```
+
+use core::{cmp, ptr, task};
+use core::cell::{Cell, RefCell};
+use core::pin::Pin;
+use core::future::Future;
+
+use critical_section::{CriticalSection, Mutex};
+
+use crate::state::TimerState;
+use crate::alloc::boxed::Box;
+use crate::alloc::vec::Vec;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+#[derive(Clone, Copy)]
+///Platform hooks driving [CustomTimer](struct.CustomTimer.html)
+pub struct Hooks {
+    ///Frequency of monotonic counter, returned by `now`, in ticks per second.
+    pub frequency: u64,
+    ///Returns current value of monotonic counter.
+    pub now: fn() -> u64,
+    ///Arms alarm to fire at specified tick, replacing previously armed one.
+    ///
+    ///When alarm fires, [on_alarm](fn.on_alarm.html) must be called.
+    ///If tick has already passed, alarm should fire as soon as possible.
+    pub arm: fn(u64),
+}
+
+struct Entry {
+    deadline: u64,
+    state: *const TimerState,
+}
+
+//Entry is only accessed within critical section, while timer keeps its state alive until entry is removed.
+unsafe impl Send for Entry {}
+
+static HOOKS: Mutex<Cell<Option<Hooks>>> = Mutex::new(Cell::new(None));
+static TIMERS: Mutex<RefCell<Vec<Entry>>> = Mutex::new(RefCell::new(Vec::new()));
+
+///Registers platform hooks.
+///
+///Must be called before any timer is started.
+pub fn init(hooks: Hooks) {
+    critical_section::with(|cs| HOOKS.borrow(cs).set(Some(hooks)))
+}
+
+///Notifies expired timers and arms alarm for the next one, if any.
+///
+///Must be called when alarm, armed via `Hooks::arm`, fires.
+///Timer's callbacks are invoked within critical section, but are allowed to restart or cancel timers.
+pub fn on_alarm() {
+    critical_section::with(|cs| {
+        let hooks = hooks(cs);
+        let now = (hooks.now)();
+
+        //Callback may modify timers, so entry is removed before its callback is invoked.
+        //Restarted timer's deadline is past `now`, therefore it is not notified again.
+        loop {
+            let state = {
+                let mut timers = TIMERS.borrow_ref_mut(cs);
+                match timers.iter().position(|entry| entry.deadline <= now) {
+                    Some(idx) => timers.swap_remove(idx).state,
+                    None => break,
+                }
+            };
+
+            unsafe {
+                (*state).wake();
+            }
+        }
+
+        if let Some(next) = TIMERS.borrow_ref(cs).iter().map(|entry| entry.deadline).min() {
+            (hooks.arm)(next);
+        }
+    })
+}
+
+#[inline]
+fn hooks(cs: CriticalSection<'_>) -> Hooks {
+    match HOOKS.borrow(cs).get() {
+        Some(hooks) => hooks,
+        None => panic!("Custom timer hooks are not initialized"),
+    }
+}
+
+fn to_ticks(duration: time::Duration, frequency: u64) -> u64 {
+    let ticks = duration.as_nanos() * frequency as u128 / NANOS_PER_SEC;
+    cmp::min(ticks, u64::max_value() as u128) as u64
+}
+
+fn to_duration(ticks: u64, frequency: u64) -> time::Duration {
+    let nanos = ticks as u128 * NANOS_PER_SEC / frequency as u128;
+    time::Duration::new((nanos / NANOS_PER_SEC) as u64, (nanos % NANOS_PER_SEC) as u32)
+}
+
+fn schedule(state: &TimerState, timeout: time::Duration) {
+    critical_section::with(|cs| {
+        let hooks = hooks(cs);
+        let deadline = (hooks.now)().saturating_add(cmp::max(to_ticks(timeout, hooks.frequency), 1));
+
+        let mut timers = TIMERS.borrow_ref_mut(cs);
+        let is_next = timers.iter().all(|entry| entry.deadline > deadline);
+        timers.push(Entry {
+            deadline,
+            state,
+        });
+
+        if is_next {
+            (hooks.arm)(deadline);
+        }
+    })
+}
+
+fn unschedule(state: &TimerState) {
+    //Alarm is not re-armed, on_alarm handles spurious notification
+    critical_section::with(|cs| {
+        TIMERS.borrow_ref_mut(cs).retain(|entry| !ptr::eq(entry.state, state));
+    })
+}
+
+///Time primitives based on [Hooks](../struct.Hooks.html) counter.
+pub mod time {
+    use core::ops;
+
+    pub use core::time::Duration;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    ///Monotonic time point in ticks of custom counter.
+    pub struct Instant(u64);
+
+    impl Instant {
+        #[inline]
+        ///Returns current time.
+        pub fn now() -> Self {
+            critical_section::with(|cs| Self((super::hooks(cs).now)()))
+        }
+
+        #[inline]
+        fn frequency() -> u64 {
+            critical_section::with(|cs| super::hooks(cs).frequency)
+        }
+
+        #[inline]
+        ///Returns duration elapsed since `earlier`, if `earlier` is not later than `self`.
+        pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+            self.0.checked_sub(earlier.0).map(|ticks| super::to_duration(ticks, Self::frequency()))
+        }
+
+        #[inline]
+        ///Returns duration elapsed since `earlier` or zero if `earlier` is later than `self`.
+        pub fn saturating_duration_since(&self, earlier: Instant) -> Duration {
+            self.checked_duration_since(earlier).unwrap_or_default()
+        }
+
+        #[inline]
+        ///Returns duration elapsed since `earlier` or zero if `earlier` is later than `self`.
+        pub fn duration_since(&self, earlier: Instant) -> Duration {
+            self.saturating_duration_since(earlier)
+        }
+
+        #[inline]
+        ///Returns duration elapsed since `self`.
+        pub fn elapsed(&self) -> Duration {
+            Self::now().saturating_duration_since(*self)
+        }
+
+        #[inline]
+        ///Returns `self + duration`, if it can be represented.
+        pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+            self.0.checked_add(super::to_ticks(duration, Self::frequency())).map(Self)
+        }
+
+        #[inline]
+        ///Returns `self - duration`, if it can be represented.
+        pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+            self.0.checked_sub(super::to_ticks(duration, Self::frequency())).map(Self)
+        }
+    }
+
+    impl ops::Add<Duration> for Instant {
+        type Output = Instant;
+
+        #[inline]
+        fn add(self, other: Duration) -> Instant {
+            self.checked_add(other).expect("overflow when adding duration to instant")
+        }
+    }
+
+    impl ops::AddAssign<Duration> for Instant {
+        #[inline]
+        fn add_assign(&mut self, other: Duration) {
+            *self = *self + other;
+        }
+    }
+
+    impl ops::Sub<Duration> for Instant {
+        type Output = Instant;
+
+        #[inline]
+        fn sub(self, other: Duration) -> Instant {
+            self.checked_sub(other).expect("overflow when subtracting duration from instant")
+        }
+    }
+
+    impl ops::Sub<Instant> for Instant {
+        type Output = Duration;
+
+        #[inline]
+        fn sub(self, other: Instant) -> Duration {
+            self.duration_since(other)
+        }
+    }
+}
+
+enum State {
+    Init(time::Duration),
+    Running(Box<TimerState>),
+}
+
+///Timer driven by application's [Hooks](struct.Hooks.html)
+///
+///Callback is invoked from [on_alarm](fn.on_alarm.html), within critical section.
+pub struct CustomTimer {
+    state: State,
+}
+
+impl CustomTimer {
+    #[inline]
+    ///Creates new instance
+    pub const fn new(time: time::Duration) -> Self {
+        Self {
+            state: State::Init(time),
+        }
+    }
+}
+
+impl super::Timer for CustomTimer {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        Self::new(timeout)
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(ref state) => !state.is_done(),
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(ref state) => state.is_done(),
+        }
+    }
+
+    fn restart(&mut self, new_value: time::Duration) {
+        assert_time!(new_value);
+
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = new_value;
+            },
+            State::Running(ref state) => {
+                unschedule(state);
+                state.reset();
+                schedule(state, new_value);
+            }
+        }
+    }
+
+    fn restart_ctx(&mut self, new_value: time::Duration, waker: &task::Waker) {
+        if let State::Running(ref state) = self.state {
+            state.register(waker);
+        }
+        self.restart(new_value)
+    }
+
+    fn cancel(&mut self) {
+        match &self.state {
+            State::Init(_) => (),
+            State::Running(ref state) => {
+                unschedule(state);
+                state.cancel();
+            }
+        }
+    }
+}
+
+impl super::SyncTimer for CustomTimer {
+    fn init<R, F: Fn(&TimerState) -> R>(&mut self, init: F) -> R {
+        if let State::Init(timeout) = self.state {
+            let state = Box::new(TimerState::new());
+            init(&state);
+
+            schedule(&state, timeout);
+
+            self.state = State::Running(state);
+        }
+
+        match &self.state {
+            State::Running(ref state) => init(state),
+            State::Init(_) => unreach!(),
+        }
+    }
+}
+
+impl Future for CustomTimer {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        crate::timer::poll_sync(self.get_mut(), ctx)
+    }
+}
+
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for CustomTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
+impl Drop for CustomTimer {
+    fn drop(&mut self) {
+        if let State::Running(ref state) = self.state {
+            unschedule(state);
+        }
+    }
+}
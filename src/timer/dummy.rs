@@ -53,3 +53,11 @@ impl Future for DummyTimer {
         unimplemented!();
     }
 }
+
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for DummyTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
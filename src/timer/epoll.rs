@@ -0,0 +1,250 @@
+//! Epoll based timer
+
+use core::{mem, task, time};
+use core::pin::Pin;
+use core::future::Future;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::std::thread;
+use crate::std::sync::{Arc, Mutex, OnceLock};
+use crate::std::collections::HashMap;
+use crate::std::os::unix::io::AsRawFd;
+
+use crate::state::TimerState;
+use super::fd::{TimerFd, RawTimer};
+
+const EVENTS_NUM: usize = 64;
+//Timer's fd is disabled after notification until it is restarted.
+const EVENTS: u32 = (libc::EPOLLIN | libc::EPOLLONESHOT) as u32;
+
+struct Poller {
+    fd: libc::c_int,
+    //Timer's state with its generation at the time of arming.
+    timers: Mutex<HashMap<u64, (Arc<TimerState>, usize)>>,
+    next_id: AtomicU64,
+}
+
+impl Poller {
+    fn global() -> &'static Self {
+        static GLOBAL: OnceLock<Poller> = OnceLock::new();
+
+        GLOBAL.get_or_init(|| {
+            let fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+            os_assert!(fd != -1);
+
+            thread::Builder::new().name("async-timer-epoll".into()).spawn(|| Self::global().run()).expect("To spawn epoll thread");
+
+            Self {
+                fd,
+                timers: Mutex::new(HashMap::new()),
+                next_id: AtomicU64::new(0),
+            }
+        })
+    }
+
+    fn ctl(&self, op: libc::c_int, fd: &RawTimer, id: u64) {
+        let mut event = libc::epoll_event {
+            events: EVENTS,
+            u64: id,
+        };
+
+        let res = unsafe { libc::epoll_ctl(self.fd, op, fd.as_raw_fd(), &mut event) };
+        os_assert!(res != -1);
+    }
+
+    fn insert(&self, state: &Arc<TimerState>, old_id: Option<u64>) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut timers = self.timers.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some(old_id) = old_id {
+            timers.remove(&old_id);
+        }
+        timers.insert(id, (state.clone(), state.generation()));
+        id
+    }
+
+    fn register(&self, fd: &RawTimer, state: &Arc<TimerState>) -> u64 {
+        let id = self.insert(state, None);
+        self.ctl(libc::EPOLL_CTL_ADD, fd, id);
+        id
+    }
+
+    //Re-registers timer under new id, so that notification of previous arming, which may be
+    //already received by `run`, is ignored.
+    //
+    //Must be called after state is reset.
+    fn rearm(&self, fd: &RawTimer, id: u64, state: &Arc<TimerState>) -> u64 {
+        let id = self.insert(state, Some(id));
+        self.ctl(libc::EPOLL_CTL_MOD, fd, id);
+        id
+    }
+
+    fn deregister(&self, fd: &RawTimer, id: u64) {
+        //Once removed, late notification is ignored by `run`
+        self.timers.lock().unwrap_or_else(|error| error.into_inner()).remove(&id);
+        self.ctl(libc::EPOLL_CTL_DEL, fd, id);
+    }
+
+    fn run(&self) {
+        let mut events: [libc::epoll_event; EVENTS_NUM] = unsafe { mem::zeroed() };
+
+        loop {
+            let num = unsafe { libc::epoll_wait(self.fd, events.as_mut_ptr(), EVENTS_NUM as _, -1) };
+            if num == -1 {
+                let error = crate::std::io::Error::last_os_error();
+                match error.kind() {
+                    crate::std::io::ErrorKind::Interrupted => continue,
+                    _ => panic!("Unexpected epoll_wait error: {}", error),
+                }
+            }
+
+            for event in &events[..num as usize] {
+                let id = event.u64;
+                let timer = self.timers.lock().unwrap_or_else(|error| error.into_inner()).get(&id).cloned();
+                //State is reset on restart, which discards notification if timer is restarted
+                //after lookup.
+                if let Some((state, generation)) = timer {
+                    state.wake_generation(generation);
+                }
+            }
+        }
+    }
+}
+
+enum State {
+    Init(time::Duration),
+    Running(RawTimer, u64, Arc<TimerState>),
+}
+
+///Timer based on `timerfd` that is driven by single `epoll` thread
+///
+///Callback is invoked from internal thread, therefore it is not limited like signal handler's one.
+pub struct EpollTimer {
+    state: State,
+}
+
+impl EpollTimer {
+    #[inline]
+    ///Creates new instance
+    pub const fn new(time: time::Duration) -> Self {
+        Self {
+            state: State::Init(time),
+        }
+    }
+}
+
+impl super::Timer for EpollTimer {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        Self::new(timeout)
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, _, ref state) => !state.is_done(),
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, _, ref state) => state.is_done(),
+        }
+    }
+
+    fn restart(&mut self, new_value: time::Duration) {
+        assert_time!(new_value);
+
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = new_value;
+            },
+            State::Running(ref mut fd, ref mut id, ref state) => {
+                state.reset();
+                //Clear previous expiration, if any, so that fd is no longer readable
+                fd.read();
+                fd.set(new_value);
+                *id = Poller::global().rearm(fd, *id, state);
+            }
+        }
+    }
+
+    fn restart_at(&mut self, deadline: crate::std::time::Instant) {
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = crate::ext::until(deadline);
+            },
+            State::Running(ref mut fd, ref mut id, ref state) => {
+                state.reset();
+                fd.read();
+                fd.set_at(deadline);
+                *id = Poller::global().rearm(fd, *id, state);
+            }
+        }
+    }
+
+    fn restart_ctx(&mut self, new_value: time::Duration, waker: &task::Waker) {
+        if let State::Running(_, _, ref state) = self.state {
+            state.register(waker);
+        }
+        self.restart(new_value)
+    }
+
+    fn cancel(&mut self) {
+        match &mut self.state {
+            State::Init(_) => (),
+            State::Running(ref mut fd, _, ref state) => {
+                state.cancel();
+                fd.unset();
+            }
+        }
+    }
+}
+
+impl super::SyncTimer for EpollTimer {
+    fn init<R, F: Fn(&TimerState) -> R>(&mut self, init: F) -> R {
+        if let State::Init(timeout) = self.state {
+            let state = Arc::new(TimerState::new());
+            init(&state);
+
+            let mut fd = RawTimer::new();
+            fd.set(timeout);
+            let id = Poller::global().register(&fd, &state);
+
+            self.state = State::Running(fd, id, state);
+        }
+
+        match &self.state {
+            State::Running(_, _, ref state) => init(state),
+            State::Init(_) => unreach!(),
+        }
+    }
+}
+
+impl Future for EpollTimer {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        crate::timer::poll_sync(self.get_mut(), ctx)
+    }
+}
+
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for EpollTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
+impl Drop for EpollTimer {
+    fn drop(&mut self) {
+        if let State::Running(ref fd, id, _) = self.state {
+            Poller::global().deregister(fd, id);
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! File descriptor based timers
+//!
+//! Shared by event loop based timers that only need to poll readiness of fd.
+
+use libc::c_int;
+
+use core::{task, time};
+use core::pin::Pin;
+use core::future::Future;
+
+use crate::std::io;
+use crate::std::time::Instant;
+use crate::std::os::unix::io::{AsRawFd, RawFd};
+
+///Timer that signals expiration by making its fd readable.
+///
+///Implemented by [RawTimer](struct.RawTimer.html) using `timerfd` on Linux/Android and `kqueue` on BSD and Apple systems.
+pub trait TimerFd: AsRawFd + crate::std::os::unix::io::AsFd + Sync + Send + Unpin {
+    ///Creates new disarmed timer.
+    fn new() -> Self;
+    ///Arms timer to expire after `time`, replacing previous value, if any.
+    fn set(&mut self, time: time::Duration);
+    ///Arms timer to expire at `deadline`, replacing previous value, if any.
+    ///
+    ///Default implementation arms timer with time remaining until `deadline`.
+    fn set_at(&mut self, deadline: Instant) {
+        //Zero value disarms timer, so make sure that elapsed deadline fires as soon as possible
+        let timeout = deadline.saturating_duration_since(Instant::now()).max(time::Duration::from_nanos(1));
+        self.set(timeout)
+    }
+    ///Disarms timer.
+    fn unset(&mut self);
+    ///Consumes expiration notification, returning number of expirations.
+    ///
+    ///Returns `0` if timer hasn't expired yet.
+    fn read(&mut self) -> usize;
+}
+
+///Wrapper over fd based timer.
+pub struct RawTimer(c_int);
+
+impl AsRawFd for RawTimer {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.0
+    }
+}
+
+impl crate::std::os::unix::io::AsFd for RawTimer {
+    #[inline(always)]
+    fn as_fd(&self) -> crate::std::os::unix::io::BorrowedFd<'_> {
+        //fd is owned by `RawTimer` and lives as long as it does
+        unsafe {
+            crate::std::os::unix::io::BorrowedFd::borrow_raw(self.0)
+        }
+    }
+}
+
+impl Drop for RawTimer {
+    #[inline(always)]
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+mod sys {
+    #[repr(C)]
+    pub struct itimerspec {
+        pub it_interval: libc::timespec,
+        pub it_value: libc::timespec,
+    }
+
+    extern "C" {
+        pub fn timerfd_create(clockid: libc::clockid_t, flags: libc::c_int) -> libc::c_int;
+        pub fn timerfd_settime(timerid: libc::c_int, flags: libc::c_int, new_value: *const itimerspec, old_value: *mut itimerspec) -> libc::c_int;
+    }
+
+    pub const TFD_NONBLOCK: libc::c_int = libc::O_NONBLOCK;
+    pub const TFD_TIMER_ABSTIME: libc::c_int = 1;
+}
+
+#[cfg(target_os = "linux")]
+use libc as sys;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl RawTimer {
+    fn settime(&mut self, flags: c_int, timeout: time::Duration) {
+        #[cfg(not(target_pointer_width = "64"))]
+        use core::convert::TryFrom;
+
+        let it_value = libc::timespec {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            #[cfg(target_pointer_width = "64")]
+            tv_nsec: libc::suseconds_t::from(timeout.subsec_nanos()),
+            #[cfg(not(target_pointer_width = "64"))]
+            tv_nsec: libc::suseconds_t::try_from(timeout.subsec_nanos()).unwrap_or(libc::suseconds_t::max_value()),
+        };
+
+        let timer = sys::itimerspec {
+            it_interval: unsafe { core::mem::MaybeUninit::zeroed().assume_init() },
+            it_value,
+        };
+
+        let ret = unsafe { sys::timerfd_settime(self.0, flags, &timer, core::ptr::null_mut()) };
+        os_assert!(ret != -1);
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl TimerFd for RawTimer {
+    fn new() -> Self {
+        let fd = unsafe { sys::timerfd_create(libc::CLOCK_MONOTONIC, sys::TFD_NONBLOCK) };
+
+        os_assert!(fd != -1);
+        Self(fd)
+    }
+
+    #[inline]
+    fn set(&mut self, timeout: time::Duration) {
+        self.settime(0, timeout);
+    }
+
+    fn set_at(&mut self, deadline: Instant) {
+        let mut now = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        //Instant relies on CLOCK_MONOTONIC too, so only offset between clock reads is lost
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        os_assert!(unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now) } == 0);
+
+        let now = time::Duration::new(now.tv_sec as u64, now.tv_nsec as u32);
+        self.settime(sys::TFD_TIMER_ABSTIME, now + remaining);
+    }
+
+    #[inline]
+    fn unset(&mut self) {
+        self.set(time::Duration::from_secs(0));
+    }
+
+    fn read(&mut self) -> usize {
+        let mut read_num = 0u64;
+        match unsafe { libc::read(self.0, &mut read_num as *mut u64 as *mut _, 8) } {
+            -1 => {
+                let error = crate::std::io::Error::last_os_error();
+                match error.kind() {
+                    crate::std::io::ErrorKind::WouldBlock => 0,
+                    _ => panic!("Unexpected read error: {}", error),
+                }
+            }
+            _ => read_num as usize,
+        }
+    }
+}
+
+#[cfg(any(target_os = "dragonfly", target_os = "freebsd", target_os = "ios", target_os = "macos", target_os = "netbsd", target_os = "openbsd"))]
+impl TimerFd for RawTimer {
+    fn new() -> Self {
+        let fd = unsafe {
+            libc::kqueue()
+        };
+
+        //If you hit this, then most likely you run into OS imposed limit on file descriptor number
+        os_assert!(fd != -1);
+        Self(fd)
+    }
+
+    fn set(&mut self, time: time::Duration) {
+        let timeout = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let mut empty = [];
+        let mut event = libc::kevent {
+            ident: 1,
+            filter: libc::EVFILT_TIMER,
+            flags: libc::EV_ADD | libc::EV_ENABLE | libc::EV_ONESHOT,
+            fflags: libc::NOTE_NSECONDS,
+            data: 0,
+            udata: core::ptr::null_mut(),
+        };
+
+        let mut time = time.as_nanos();
+        if time > isize::max_value() as u128 {
+            event.fflags = libc::NOTE_USECONDS;
+            time /= 1_000;
+        }
+        if time > isize::max_value() as u128 {
+            event.fflags = 0; //default value is ms
+            time /= 1_000;
+        }
+        if time > isize::max_value() as u128 {
+            event.fflags = libc::NOTE_SECONDS;
+            time /= 1_000;
+        }
+
+        event.data = time as _;
+        let set = unsafe {
+            libc::kevent(self.0, &event, 1, empty.as_mut_ptr(), 0, &timeout)
+        };
+        os_assert!(set != -1);
+    }
+
+    fn unset(&mut self) {
+        let timeout = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let mut empty = [];
+        let event = libc::kevent {
+            ident: 1,
+            filter: libc::EVFILT_TIMER,
+            flags: libc::EV_DELETE,
+            fflags: 0,
+            data: 0,
+            udata: core::ptr::null_mut(),
+        };
+        let unset = unsafe {
+            libc::kevent(self.0, &event, 1, empty.as_mut_ptr(), 0, &timeout)
+        };
+        os_assert!(unset != -1);
+    }
+
+    fn read(&mut self) -> usize {
+        let timeout = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let empty = [];
+        let mut event = libc::kevent {
+            ident: 0,
+            filter: libc::EVFILT_TIMER,
+            flags: 0,
+            fflags: 0,
+            data: 0,
+            udata: core::ptr::null_mut(),
+        };
+        let read = unsafe {
+            libc::kevent(self.0, empty.as_ptr(), 0, &mut event, 1, &timeout)
+        };
+        os_assert!(read != -1);
+        read as _
+    }
+}
+
+///Event loop's interface to watch readiness of [TimerFd](trait.TimerFd.html).
+///
+///Implementing it allows to use [ReactorTimer](struct.ReactorTimer.html) with any event loop.
+pub trait Reactor: Sized + Send + Sync + Unpin {
+    ///Registers `fd` for readability notifications, returning its registration.
+    fn register(fd: RawFd) -> io::Result<Self>;
+
+    ///Polls registered fd for readability, arranging to wake current task when it becomes ready.
+    ///
+    ///Once `Ready` is returned, readiness is considered consumed and following calls are expected
+    ///to return `Pending` until event loop observes readiness again.
+    fn poll_read_ready(&mut self, ctx: &mut task::Context) -> task::Poll<io::Result<()>>;
+
+    ///Removes fd from event loop.
+    ///
+    ///Called before fd is closed.
+    fn deregister(self);
+}
+
+enum State<T, R> {
+    Init(time::Duration),
+    Running(T, Option<R>, bool),
+}
+
+///Timer driven by arbitrary event loop via [Reactor](trait.Reactor.html)
+///
+///Event loop's registration is created on first poll.
+pub struct ReactorTimer<R: Reactor, T: TimerFd = RawTimer> {
+    state: State<T, R>,
+}
+
+impl<R: Reactor> ReactorTimer<R, RawTimer> {
+    #[inline]
+    ///Creates new instance
+    pub const fn new(time: time::Duration) -> Self {
+        Self {
+            state: State::Init(time),
+        }
+    }
+}
+
+impl<R: Reactor, T: TimerFd> super::Timer for ReactorTimer<R, T> {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        Self {
+            state: State::Init(timeout),
+        }
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, _, state) => !*state,
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, _, state) => *state
+        }
+    }
+
+    fn restart(&mut self, new_value: time::Duration) {
+        assert_time!(new_value);
+
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = new_value;
+            },
+            State::Running(ref mut fd, _, ref mut state) => {
+                *state = false;
+                fd.set(new_value);
+            }
+        }
+    }
+
+    fn restart_at(&mut self, deadline: Instant) {
+        match &mut self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = crate::ext::until(deadline);
+            },
+            State::Running(ref mut fd, _, ref mut state) => {
+                *state = false;
+                fd.set_at(deadline);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn restart_ctx(&mut self, new_value: time::Duration, _: &task::Waker) {
+        self.restart(new_value)
+    }
+
+    fn cancel(&mut self) {
+        match &mut self.state {
+            State::Init(_) => (),
+            State::Running(ref mut fd, _, ref mut state) => {
+                *state = true;
+                fd.unset();
+            }
+        }
+    }
+}
+
+impl<R: Reactor, T: TimerFd> Future for ReactorTimer<R, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        if let State::Init(ref timeout) = &self.state {
+            let mut fd = T::new();
+            let registration = R::register(fd.as_raw_fd()).expect("To register timer's fd");
+            fd.set(*timeout);
+            self.state = State::Running(fd, Some(registration), false)
+        };
+
+        if let State::Running(ref mut fd, Some(ref mut registration), ref mut state) = &mut self.state {
+            if *state {
+                return task::Poll::Ready(());
+            }
+
+            loop {
+                match registration.poll_read_ready(ctx) {
+                    task::Poll::Pending => return task::Poll::Pending,
+                    task::Poll::Ready(ready) => {
+                        ready.expect("Unable to read timer's fd");
+
+                        match fd.read() {
+                            //Spurious wake up, poll again to register interest
+                            0 => continue,
+                            _ => {
+                                *state = true;
+                                return task::Poll::Ready(())
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            unreach!();
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<R: Reactor, T: TimerFd> futures_core_03::future::FusedFuture for ReactorTimer<R, T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
+impl<R: Reactor, T: TimerFd> Drop for ReactorTimer<R, T> {
+    fn drop(&mut self) {
+        if let State::Running(_, ref mut registration, _) = self.state {
+            if let Some(registration) = registration.take() {
+                registration.deregister();
+            }
+        }
+    }
+}
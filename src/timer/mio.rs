@@ -0,0 +1,143 @@
+use mio_1 as mio;
+
+use mio::{Interest, Registry, Token};
+use mio::event::{Event, Source};
+use mio::unix::SourceFd;
+
+use core::time;
+
+use crate::std::io;
+use crate::std::time::Instant;
+use crate::std::os::unix::io::AsRawFd;
+
+use super::fd::{TimerFd, RawTimer};
+
+impl Source for RawTimer {
+    #[inline]
+    fn register(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    #[inline]
+    fn reregister(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    #[inline]
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
+///Timer to be used within `mio` event loop.
+///
+///Wraps [RawTimer](struct.RawTimer.html) together with its `Token`, so that it can be registered
+///with `mio::Poll` and its events recognized.
+///
+///## Usage
+///
+///```no_run
+///use async_timer::timer::MioTimer;
+///use mio_1 as mio;
+///
+///use core::time;
+///
+///let mut poll = mio::Poll::new().expect("To create poll");
+///let mut events = mio::Events::with_capacity(8);
+///
+///let mut timer = MioTimer::new(mio::Token(0));
+///timer.register(poll.registry()).expect("To register timer");
+///timer.set(time::Duration::from_millis(10));
+///
+///poll.poll(&mut events, None).expect("To poll");
+///for event in events.iter() {
+///    if timer.is_expired_by(event) {
+///        assert!(timer.read() > 0);
+///    }
+///}
+///```
+pub struct MioTimer {
+    fd: RawTimer,
+    token: Token,
+}
+
+impl MioTimer {
+    #[inline]
+    ///Creates new disarmed timer identified by `token`.
+    pub fn new(token: Token) -> Self {
+        Self {
+            fd: RawTimer::new(),
+            token,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns `Token` of the timer.
+    pub fn token(&self) -> Token {
+        self.token
+    }
+
+    #[inline]
+    ///Registers timer for readability with `registry`, using timer's `Token`.
+    pub fn register(&mut self, registry: &Registry) -> io::Result<()> {
+        registry.register(&mut self.fd, self.token, Interest::READABLE)
+    }
+
+    #[inline]
+    ///Removes timer from `registry`.
+    pub fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        registry.deregister(&mut self.fd)
+    }
+
+    #[inline]
+    ///Arms timer to expire after `timeout`.
+    pub fn set(&mut self, timeout: time::Duration) {
+        assert_time!(timeout);
+        self.fd.set(timeout)
+    }
+
+    #[inline]
+    ///Arms timer to expire at `deadline`.
+    pub fn set_at(&mut self, deadline: Instant) {
+        self.fd.set_at(deadline)
+    }
+
+    #[inline]
+    ///Disarms timer.
+    pub fn unset(&mut self) {
+        self.fd.unset()
+    }
+
+    #[inline]
+    ///Consumes expirations, returning its number.
+    ///
+    ///Should be called on every event as `mio` notifications are edge triggered.
+    pub fn read(&mut self) -> usize {
+        self.fd.read()
+    }
+
+    #[inline]
+    ///Returns whether `event` belongs to this timer.
+    pub fn is_expired_by(&self, event: &Event) -> bool {
+        event.token() == self.token && event.is_readable()
+    }
+}
+
+impl Source for MioTimer {
+    #[inline]
+    fn register(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        self.token = token;
+        self.fd.register(registry, token, interests)
+    }
+
+    #[inline]
+    fn reregister(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        self.token = token;
+        self.fd.reregister(registry, token, interests)
+    }
+
+    #[inline]
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.fd.deregister(registry)
+    }
+}
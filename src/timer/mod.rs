@@ -5,6 +5,13 @@ use core::future::Future;
 
 use crate::state::TimerState;
 
+#[cfg(any(unix, windows))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+#[cfg(all(feature = "custom", not(any(unix, windows, target_arch = "wasm32"))))]
+use custom::time::Instant;
+
 ///Timer
 ///
 ///## Common implementations:
@@ -12,7 +19,12 @@ use crate::state::TimerState;
 ///- Windows uses thread pooled timer
 ///- Apple systems uses dispatch source API
 ///- Posix compatible `timer_create`, available on major Posix-compliant systems. Depends on availability of `siginfo_t::si_value` method.
+///- With `std` feature, Linux uses `timerfd` driven by single `epoll` thread as `SyncTimer`, avoiding signals.
 ///- Wasm uses Web API `SetTimeout`
+///- With `tokio1` or `async-io` features, unix systems use `timerfd` or `kqueue` driven by event loop.
+///- With `std` feature, unix systems provide `ReactorTimer` to drive `timerfd` or `kqueue` by custom event loop.
+///- With `io-uring` feature, Linux provides `UringTimer` that shares single `io_uring` among many timers.
+///- With `custom` feature, targets without OS use `CustomTimer` driven by application's hooks.
 ///- Dummy timer is used  when no implementation is available. Panics when used.
 ///
 ///## Usage
@@ -51,6 +63,18 @@ pub trait Timer: Send + Sync + Unpin + Future<Output=()> {
     ///Restarts timer with new timeout value and waker.
     fn restart_ctx(&mut self, timeout: time::Duration, waker: &task::Waker);
 
+    #[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+    #[inline]
+    ///Restarts timer to expire at `deadline`.
+    ///
+    ///If `deadline` is already reached, timer expires as soon as possible.
+    ///
+    ///Default implementation converts `deadline` into timeout, while timers capable of arming
+    ///absolute deadline (e.g. `timerfd` with `TFD_TIMER_ABSTIME`) avoid latency of such conversion.
+    fn restart_at(&mut self, deadline: Instant) {
+        self.restart(crate::ext::until(deadline))
+    }
+
     ///Cancels timer, if it is still ongoing.
     fn cancel(&mut self);
 }
@@ -115,10 +139,51 @@ pub trait SyncTimer: Timer {
     fn tick(&mut self) -> bool {
         self.init(|state| state.is_done())
     }
+
+    #[cfg(any(unix, windows))]
+    ///Blocks current thread until timer expires.
+    ///
+    ///Starts timer, if necessary, and replaces registered callback with one that unparks current thread.
+    fn wait_blocking(&mut self) {
+        //Waker is kept alive by caller, so that callback never drops its last reference
+        let waker = crate::state::thread_waker();
+        self.init(|state| state.register(&waker));
+
+        while !self.tick() {
+            std::thread::park();
+        }
+    }
+
+    #[cfg(any(unix, windows))]
+    ///Blocks current thread until timer expires or `timeout` elapses.
+    ///
+    ///Starts timer, if necessary, and replaces registered callback with one that unparks current thread.
+    ///
+    ///Returns whether timer has expired.
+    fn wait_blocking_timeout(&mut self, timeout: time::Duration) -> bool {
+        let deadline = std::time::Instant::now() + timeout;
+        //Waker is kept alive by caller, so that callback never drops its last reference
+        let waker = crate::state::thread_waker();
+        self.init(|state| state.register(&waker));
+
+        loop {
+            if self.tick() {
+                return true;
+            }
+
+            match deadline.checked_duration_since(std::time::Instant::now()) {
+                Some(remaining) if remaining > time::Duration::from_secs(0) => std::thread::park_timeout(remaining),
+                _ => return self.tick(),
+            }
+        }
+    }
 }
 
 #[inline(always)]
-fn poll_sync<T: SyncTimer>(timer: &mut T, ctx: &mut task::Context) -> task::Poll<()> {
+///Polls `SyncTimer`, registering context's `Waker` as callback.
+///
+///Intended to implement `Future` for `SyncTimer` implementations.
+pub fn poll_sync<T: SyncTimer>(timer: &mut T, ctx: &mut task::Context) -> task::Poll<()> {
     timer.init(|state| {
         state.register(ctx.waker());
         match state.is_done() {
@@ -139,30 +204,61 @@ pub type Platform = win::WinTimer;
 ///Platform alias to Windows timer
 pub type SyncPlatform = win::WinTimer;
 
+#[cfg(all(feature = "std", unix))]
+mod fd;
+#[cfg(all(feature = "std", unix))]
+pub use fd::{TimerFd, RawTimer, Reactor, ReactorTimer};
+
+#[cfg(all(feature = "mio", unix))]
+mod mio;
+#[cfg(all(feature = "mio", unix))]
+pub use self::mio::MioTimer;
+
 #[cfg(all(feature = "tokio1", unix))]
 mod async_tokio1;
 #[cfg(all(feature = "tokio1", unix))]
-pub use async_tokio1::AsyncTimer;
+pub use async_tokio1::{AsyncTimer, TimerPool, PoolStats};
 #[cfg(all(feature = "tokio1", unix))]
 ///Timer based on tokio's `AsyncFd`
 pub type Platform = AsyncTimer;
 
+#[cfg(all(feature = "async-io", unix))]
+mod async_io;
+#[cfg(all(feature = "async-io", unix))]
+pub use async_io::AsyncIoTimer;
+#[cfg(all(feature = "async-io", not(feature = "tokio1"), unix))]
+///Timer based on `async-io` reactor
+pub type Platform = AsyncIoTimer;
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub use uring::{UringTimer, Ring};
+
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
 mod posix;
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
-pub use posix::PosixTimer;
-#[cfg(all(not(feature = "tokio1"), not(any(target_os = "macos", target_os = "ios")), unix))]
+pub use posix::{PosixTimer, Clock};
+#[cfg(all(not(any(feature = "tokio1", feature = "async-io")), not(any(target_os = "macos", target_os = "ios")), unix))]
 ///Platform alias to POSIX timer
 pub type Platform = posix::PosixTimer;
-#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios")), not(all(feature = "std", any(target_os = "linux", target_os = "android")))))]
 ///Platform alias to POSIX Timer
 pub type SyncPlatform = posix::PosixTimer;
 
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+mod epoll;
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+pub use epoll::EpollTimer;
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+///Platform alias to `epoll` driven `timerfd` Timer
+pub type SyncPlatform = epoll::EpollTimer;
+
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 mod apple;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub use apple::AppleTimer;
-#[cfg(all(not(feature = "tokio1"), any(target_os = "macos", target_os = "ios")))]
+#[cfg(all(not(any(feature = "tokio1", feature = "async-io")), any(target_os = "macos", target_os = "ios")))]
 ///Platform alias to Apple Dispatch timer
 pub type Platform = apple::AppleTimer;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
@@ -180,15 +276,39 @@ pub type Platform = web::WebTimer;
 ///Platform alias to WASM Timer
 pub type SyncPlatform = web::WebTimer;
 
+#[cfg(feature = "custom")]
+pub mod custom;
+#[cfg(feature = "custom")]
+pub use custom::CustomTimer;
+#[cfg(all(feature = "custom", not(any(windows, target_arch = "wasm32", unix))))]
+///Platform alias to Custom Timer as no OS implementation is available.
+pub type Platform = custom::CustomTimer;
+#[cfg(all(feature = "custom", not(any(windows, target_arch = "wasm32", unix))))]
+///Platform alias to Custom Timer as no OS implementation is available.
+pub type SyncPlatform = custom::CustomTimer;
+
 mod dummy;
 pub use dummy::DummyTimer;
-#[cfg(not(any(windows, target_arch = "wasm32", unix)))]
+#[cfg(all(not(feature = "custom"), not(any(windows, target_arch = "wasm32", unix))))]
 ///Platform alias to Dummy Timer as no OS implementation is available.
 pub type Platform = dummy::DummyTimer;
-#[cfg(not(any(windows, target_arch = "wasm32", unix)))]
+#[cfg(all(not(feature = "custom"), not(any(windows, target_arch = "wasm32", unix))))]
 ///Platform alias to Dummy Timer as no OS implementation is available.
 pub type SyncPlatform = dummy::DummyTimer;
 
+mod backend;
+pub use backend::{TimerBackend, BackendTimer};
+
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+mod precision;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+pub use precision::{PrecisionTimer, SpinMode, DEFAULT_THRESHOLD};
+
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+mod calibrated;
+#[cfg(any(unix, windows, target_arch = "wasm32", feature = "custom"))]
+pub use calibrated::{CalibratedTimer, Calibrator};
+
 #[inline]
 ///Creates new timer, timer type depends on platform.
 pub const fn new_timer(timeout: time::Duration) -> Platform {
@@ -1,6 +1,7 @@
 //! Posix based timer
 
 use core::{mem, ptr, time, task};
+use core::sync::atomic::{AtomicUsize, Ordering};
 use core::pin::Pin;
 use core::future::Future;
 
@@ -51,7 +52,7 @@ fn init_sig() {
 
     let timer_sig = libc::sigaction {
         sa_flags: libc::SA_SIGINFO,
-        sa_sigaction: ffi::timer_handler as usize,
+        sa_sigaction: ffi::timer_handler as *const () as usize,
         sa_mask: unsafe { sa_mask.assume_init() },
         #[cfg(any(target_os = "linux", target_os = "android"))]
         sa_restorer: None,
@@ -62,15 +63,45 @@ fn init_sig() {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Clock used to measure [PosixTimer](struct.PosixTimer.html) timeout.
+pub struct Clock(libc::clockid_t);
+
+impl Clock {
+    ///System-wide wall clock, used by default.
+    pub const REALTIME: Clock = Clock(libc::CLOCK_REALTIME);
+    ///Monotonic clock, unaffected by changes of system time.
+    pub const MONOTONIC: Clock = Clock(libc::CLOCK_MONOTONIC);
+    ///CPU time consumed by all threads of the process.
+    pub const PROCESS_CPUTIME: Clock = Clock(libc::CLOCK_PROCESS_CPUTIME_ID);
+    ///CPU time consumed by the thread which starts the timer.
+    pub const THREAD_CPUTIME: Clock = Clock(libc::CLOCK_THREAD_CPUTIME_ID);
+
+    ///Returns CPU time clock of specified thread, using `pthread_getcpuclockid`.
+    pub fn thread(thread: libc::pthread_t) -> Result<Self, error_code::ErrorCode> {
+        let mut clock = mem::MaybeUninit::<libc::clockid_t>::uninit();
+        match unsafe { libc::pthread_getcpuclockid(thread, clock.as_mut_ptr()) } {
+            0 => Ok(Clock(unsafe { clock.assume_init() })),
+            error => Err(error_code::ErrorCode::new_posix(error)),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns underlying clock id.
+    pub const fn id(&self) -> libc::clockid_t {
+        self.0
+    }
+}
+
 #[cfg(feature = "c_wrapper")]
-fn time_create(state: *mut TimerState) -> ffi::timer_t {
+fn time_create(clock: Clock, state: *mut TimerState) -> ffi::timer_t {
     #[link(name = "posix_wrapper", kind = "static")]
     extern "C" {
-        fn posix_timer(_: Option<unsafe extern "C" fn(value: libc::sigval)>, _: *mut libc::c_void) -> ffi::timer_t;
+        fn posix_timer(_: libc::clockid_t, _: Option<unsafe extern "C" fn(value: libc::sigval)>, _: *mut libc::c_void) -> ffi::timer_t;
     }
 
     let res = unsafe {
-        posix_timer(Some(ffi::timer_handler), state as *mut libc::c_void)
+        posix_timer(clock.0, Some(ffi::timer_handler), state as *mut libc::c_void)
     };
 
     os_assert!(res != 0);
@@ -78,7 +109,7 @@ fn time_create(state: *mut TimerState) -> ffi::timer_t {
 }
 
 #[cfg(not(feature = "c_wrapper"))]
-fn time_create(state: *mut TimerState) -> ffi::timer_t {
+fn time_create(clock: Clock, state: *mut TimerState) -> ffi::timer_t {
     let mut event: libc::sigevent = unsafe { mem::zeroed() };
 
     event.sigev_value = libc::sigval {
@@ -94,7 +125,7 @@ fn time_create(state: *mut TimerState) -> ffi::timer_t {
     let mut res = mem::MaybeUninit::<ffi::timer_t>::uninit();
 
     unsafe {
-        os_assert!(ffi::timer_create(libc::CLOCK_REALTIME, &mut event, res.as_mut_ptr()) == 0);
+        os_assert!(ffi::timer_create(clock.0, &mut event, res.as_mut_ptr()) == 0);
         res.assume_init()
     }
 }
@@ -118,9 +149,100 @@ fn set_timer_value(fd: ffi::timer_t, timeout: time::Duration) {
     }
 }
 
+fn clock_now(clock: Clock) -> time::Duration {
+    let mut now = mem::MaybeUninit::<libc::timespec>::uninit();
+    unsafe {
+        os_assert!(libc::clock_gettime(clock.0, now.as_mut_ptr()) == 0);
+        let now = now.assume_init();
+        time::Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+    }
+}
+
+//Kernel timers are not inherited by child process, so timers created before `fork` must not be
+//used in child, as their ids may belong to different timers.
+static FORK_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn on_fork_child() {
+    FORK_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+fn init_runtime() {
+    #[cfg(not(feature = "c_wrapper"))]
+    init_sig();
+
+    unsafe {
+        os_assert!(libc::pthread_atfork(None, None, Some(on_fork_child)) == 0);
+    }
+}
+
+struct RawTimer {
+    fd: ffi::timer_t,
+    clock: Clock,
+    generation: usize,
+    timeout: time::Duration,
+    //Time of expiration according to `clock`
+    deadline: time::Duration,
+}
+
+impl RawTimer {
+    fn new(clock: Clock, state: *mut TimerState) -> Self {
+        Self {
+            fd: time_create(clock, state),
+            clock,
+            generation: FORK_GENERATION.load(Ordering::Relaxed),
+            timeout: time::Duration::from_secs(0),
+            deadline: time::Duration::from_secs(0),
+        }
+    }
+
+    #[inline(always)]
+    fn is_inherited(&self) -> bool {
+        self.generation != FORK_GENERATION.load(Ordering::Relaxed)
+    }
+
+    fn set(&mut self, timeout: time::Duration) {
+        self.timeout = timeout;
+        self.deadline = clock_now(self.clock) + timeout;
+        set_timer_value(self.fd, timeout);
+    }
+
+    fn unset(&mut self) {
+        unsafe {
+            ffi::timer_settime(self.fd, 0, &mut mem::zeroed(), ptr::null_mut());
+        }
+    }
+
+    ///Re-creates timer inherited from parent process, preserving remaining time.
+    fn revalidate(&mut self, state: &TimerState) {
+        if !self.is_inherited() {
+            return;
+        }
+
+        let timeout = self.timeout;
+        //CPU time clocks start anew in child, hence remaining time cannot exceed original timeout.
+        let remaining = self.deadline.saturating_sub(clock_now(self.clock)).min(timeout);
+
+        *self = Self::new(self.clock, state as *const TimerState as *mut TimerState);
+        if !state.is_done() {
+            //Zero value disarms timer, so elapsed deadline should fire as soon as possible
+            self.set(remaining.max(time::Duration::from_nanos(1)));
+        }
+    }
+}
+
+impl Drop for RawTimer {
+    fn drop(&mut self) {
+        if !self.is_inherited() {
+            unsafe {
+                ffi::timer_delete(self.fd);
+            }
+        }
+    }
+}
+
 enum State {
-    Init(time::Duration),
-    Running(ffi::timer_t, Box<TimerState>),
+    Init(Clock, time::Duration),
+    Running(RawTimer, Box<TimerState>),
 }
 
 ///Posix Timer
@@ -130,6 +252,14 @@ enum State {
 ///
 ///Without it, callback is called from signal handler which limits usable operations within the
 ///callback.
+///
+///By default timer measures wall time, but it can be configured to use different [Clock](struct.Clock.html),
+///e.g. to expire once process consumes specified amount of CPU time.
+///
+///## Fork
+///
+///Kernel timers are not inherited by child process, therefore running timers are transparently
+///re-created with remaining time on first use within child process.
 pub struct PosixTimer {
     state: State,
 }
@@ -138,8 +268,16 @@ impl PosixTimer {
     #[inline]
     ///Creates new instance
     pub const fn new(time: time::Duration) -> Self {
+        Self::with_clock(Clock::REALTIME, time)
+    }
+
+    #[inline]
+    ///Creates new instance, which measures timeout using specified clock.
+    ///
+    ///Note that thread CPU time clock is resolved when timer starts, i.e. on first poll.
+    pub const fn with_clock(clock: Clock, time: time::Duration) -> Self {
         Self {
-            state: State::Init(time),
+            state: State::Init(clock, time),
         }
     }
 }
@@ -155,7 +293,7 @@ impl super::Timer for PosixTimer {
     #[inline]
     fn is_ticking(&self) -> bool {
         match &self.state {
-            State::Init(_) => false,
+            State::Init(..) => false,
             State::Running(_, ref state) => !state.is_done(),
         }
     }
@@ -163,7 +301,7 @@ impl super::Timer for PosixTimer {
     #[inline]
     fn is_expired(&self) -> bool {
         match &self.state {
-            State::Init(_) => false,
+            State::Init(..) => false,
             State::Running(_, ref state) => state.is_done(),
         }
     }
@@ -172,12 +310,13 @@ impl super::Timer for PosixTimer {
         assert_time!(new_value);
 
         match &mut self.state {
-            State::Init(ref mut timeout) => {
+            State::Init(_, ref mut timeout) => {
                 *timeout = new_value;
             },
-            State::Running(fd, ref mut state) => {
+            State::Running(ref mut fd, ref mut state) => {
+                fd.revalidate(state);
                 state.reset();
-                set_timer_value(*fd, new_value);
+                fd.set(new_value);
             }
         }
     }
@@ -186,23 +325,25 @@ impl super::Timer for PosixTimer {
         assert_time!(new_value);
 
         match &mut self.state {
-            State::Init(ref mut timeout) => {
+            State::Init(_, ref mut timeout) => {
                 *timeout = new_value;
             },
-            State::Running(fd, ref mut state) => {
+            State::Running(ref mut fd, ref mut state) => {
+                fd.revalidate(state);
                 state.register(waker);
                 state.reset();
-                set_timer_value(*fd, new_value);
+                fd.set(new_value);
             }
         }
     }
 
     fn cancel(&mut self) {
         match self.state {
-            State::Init(_) => (),
-            State::Running(fd, ref state) => unsafe {
+            State::Init(..) => (),
+            State::Running(ref mut fd, ref state) => {
+                fd.revalidate(state);
                 state.cancel();
-                ffi::timer_settime(fd, 0, &mut mem::zeroed(), ptr::null_mut());
+                fd.unset();
             }
         }
     }
@@ -210,28 +351,30 @@ impl super::Timer for PosixTimer {
 
 impl super::SyncTimer for PosixTimer {
     fn init<R, F: Fn(&TimerState) -> R>(&mut self, init: F) -> R {
-        #[cfg(not(feature = "c_wrapper"))]
         {
             extern crate std;
             static RUNTIME: std::sync::Once = std::sync::Once::new();
-            RUNTIME.call_once(init_sig);
+            RUNTIME.call_once(init_runtime);
         }
 
-        if let State::Init(timeout) = self.state {
+        if let State::Init(clock, timeout) = self.state {
             let state = Box::into_raw(Box::new(TimerState::new()));
-            let fd = time_create(state);
+            let mut fd = RawTimer::new(clock, state);
 
             let state = unsafe { Box::from_raw(state) };
             init(&state);
 
-            set_timer_value(fd, timeout);
+            fd.set(timeout);
 
             self.state = State::Running(fd, state)
         }
 
-        match &self.state {
-            State::Running(_, ref state) => init(state),
-            State::Init(_) => unreach!(),
+        match &mut self.state {
+            State::Running(ref mut fd, ref state) => {
+                fd.revalidate(state);
+                init(state)
+            },
+            State::Init(..) => unreach!(),
         }
     }
 }
@@ -245,13 +388,10 @@ impl Future for PosixTimer {
     }
 }
 
-impl Drop for PosixTimer {
-    fn drop(&mut self) {
-        match self.state {
-            State::Init(_) => (),
-            State::Running(fd, _) => unsafe {
-                ffi::timer_delete(fd);
-            }
-        }
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for PosixTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
     }
 }
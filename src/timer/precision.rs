@@ -0,0 +1,226 @@
+//! Precision timer
+
+#[cfg(any(unix, windows))]
+use std::time;
+#[cfg(target_arch = "wasm32")]
+use web_time as time;
+#[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+use crate::timer::custom::time;
+use core::{hint, task};
+use core::future::Future;
+use core::pin::Pin;
+
+use super::Timer;
+use super::Platform as PlatformTimer;
+
+///Default time before deadline when [PrecisionTimer](struct.PrecisionTimer.html) stops relying on underlying timer.
+pub const DEFAULT_THRESHOLD: time::Duration = time::Duration::from_millis(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Describes how [PrecisionTimer](struct.PrecisionTimer.html) waits for remaining time after
+///underlying timer expires.
+pub enum SpinMode {
+    ///Busy waits within `poll`, blocking executor's thread.
+    Spin,
+    ///Wakes itself and returns `Pending`, allowing executor to run other tasks.
+    Yield,
+}
+
+///Timer that combines underlying timer with spin waiting
+///
+///Underlying timer is armed to expire `threshold` earlier than requested deadline, after which
+///remaining time is waited by spinning or yielding, according to [SpinMode](enum.SpinMode.html).
+///This trades CPU time for accuracy, which is mostly limited by the clock's resolution.
+///
+///## Usage
+///
+///```rust, no_run
+///use async_timer::timer::{PrecisionTimer, SpinMode};
+///
+///use core::time;
+///
+///async fn frame() {
+///    let mut timer = PrecisionTimer::platform_new(time::Duration::from_micros(16_667), time::Duration::from_millis(1), SpinMode::Yield);
+///    (&mut timer).await;
+///    println!("Expired {:?} late", timer.error());
+///}
+///```
+pub struct PrecisionTimer<T=PlatformTimer> {
+    timer: Option<T>,
+    //Whether underlying timer is used for current deadline.
+    armed: bool,
+    timeout: time::Duration,
+    threshold: time::Duration,
+    mode: SpinMode,
+    deadline: Option<time::Instant>,
+    error: Option<time::Duration>,
+}
+
+impl PrecisionTimer {
+    #[inline(always)]
+    ///Creates new instance using platform timer
+    pub fn platform_new(timeout: time::Duration, threshold: time::Duration, mode: SpinMode) -> Self {
+        PrecisionTimer::<PlatformTimer>::with_threshold(timeout, threshold, mode)
+    }
+}
+
+impl<T: Timer> PrecisionTimer<T> {
+    ///Creates new instance with specified threshold and spin mode.
+    pub fn with_threshold(timeout: time::Duration, threshold: time::Duration, mode: SpinMode) -> Self {
+        assert_time!(timeout);
+        Self {
+            timer: None,
+            armed: false,
+            timeout,
+            threshold,
+            mode,
+            deadline: None,
+            error: None,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns threshold, that is time before deadline when timer starts spinning.
+    pub fn threshold(&self) -> time::Duration {
+        self.threshold
+    }
+
+    #[inline(always)]
+    ///Sets threshold, change is reflected on next restart of timer.
+    pub fn set_threshold(&mut self, threshold: time::Duration) {
+        self.threshold = threshold;
+    }
+
+    #[inline(always)]
+    ///Returns deadline of the timer, if it is started.
+    pub fn deadline(&self) -> Option<time::Instant> {
+        self.deadline
+    }
+
+    #[inline(always)]
+    ///Returns how late timer expired in relation to deadline.
+    ///
+    ///Available only once timer expires.
+    pub fn error(&self) -> Option<time::Duration> {
+        self.error
+    }
+
+    fn arm(&mut self, waker: Option<&task::Waker>) {
+        self.deadline = Some(time::Instant::now() + self.timeout);
+        self.error = None;
+
+        self.armed = match self.timeout.checked_sub(self.threshold) {
+            Some(early) if early > time::Duration::from_secs(0) => {
+                match (self.timer.as_mut(), waker) {
+                    (Some(timer), Some(waker)) => timer.restart_ctx(early, waker),
+                    (Some(timer), None) => timer.restart(early),
+                    (None, _) => self.timer = Some(T::new(early)),
+                }
+                true
+            },
+            _ => false,
+        };
+    }
+}
+
+impl<T: Timer> Timer for PrecisionTimer<T> {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        Self::with_threshold(timeout, DEFAULT_THRESHOLD, SpinMode::Spin)
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        self.deadline.is_some() && self.error.is_none()
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.error.is_some()
+    }
+
+    fn restart(&mut self, timeout: time::Duration) {
+        assert_time!(timeout);
+
+        self.timeout = timeout;
+        if self.deadline.is_some() {
+            self.arm(None);
+        }
+    }
+
+    fn restart_ctx(&mut self, timeout: time::Duration, waker: &task::Waker) {
+        assert_time!(timeout);
+
+        self.timeout = timeout;
+        if self.deadline.is_some() {
+            self.arm(Some(waker));
+        }
+    }
+
+    fn cancel(&mut self) {
+        if let Some(timer) = self.timer.as_mut() {
+            if self.armed {
+                timer.cancel();
+            }
+        }
+        self.armed = false;
+        if self.deadline.is_some() {
+            self.error = Some(time::Duration::from_secs(0));
+        }
+    }
+}
+
+impl<T: Timer> Future for PrecisionTimer<T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.error.is_some() {
+            return task::Poll::Ready(());
+        }
+
+        let deadline = match this.deadline {
+            Some(deadline) => deadline,
+            None => {
+                this.arm(None);
+                match this.deadline {
+                    Some(deadline) => deadline,
+                    None => unreach!(),
+                }
+            }
+        };
+
+        if this.armed {
+            if let Some(timer) = this.timer.as_mut() {
+                match Future::poll(Pin::new(timer), ctx) {
+                    task::Poll::Pending => return task::Poll::Pending,
+                    task::Poll::Ready(()) => this.armed = false,
+                }
+            }
+        }
+
+        loop {
+            if let Some(error) = time::Instant::now().checked_duration_since(deadline) {
+                this.error = Some(error);
+                return task::Poll::Ready(());
+            }
+
+            match this.mode {
+                SpinMode::Spin => hint::spin_loop(),
+                SpinMode::Yield => {
+                    ctx.waker().wake_by_ref();
+                    return task::Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: Timer> futures_core_03::future::FusedFuture for PrecisionTimer<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        Timer::is_expired(self)
+    }
+}
@@ -0,0 +1,304 @@
+//! io_uring based timer
+
+use io_uring_07 as io_uring;
+
+use io_uring::{opcode, squeue, types, IoUring, EnterFlags};
+
+use core::{task, time};
+use core::pin::Pin;
+use core::future::Future;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::std::io;
+use crate::std::thread;
+use crate::std::sync::{Arc, Mutex, OnceLock};
+use crate::std::collections::HashMap;
+
+use crate::state::TimerState;
+
+///`user_data` used to stop completion thread.
+const SHUTDOWN: u64 = u64::MAX;
+///`user_data` of `IORING_OP_TIMEOUT_REMOVE` entries, which completions are ignored.
+const REMOVE: u64 = u64::MAX - 1;
+///Number of entries in default ring.
+const DEFAULT_ENTRIES: u32 = 256;
+
+struct Inner {
+    ring: IoUring,
+    //Submission queue can be accessed by single thread at a time
+    submission: Mutex<()>,
+    //Timer's state with its generation at the time of arming.
+    timers: Mutex<HashMap<u64, (Arc<TimerState>, usize)>>,
+    next_id: AtomicU64,
+}
+
+impl Inner {
+    //Returns once entry is consumed by kernel, as it may reference memory owned by caller, like `Timespec`.
+    fn submit(&self, entry: squeue::Entry) {
+        let _guard = self.submission.lock().unwrap_or_else(|error| error.into_inner());
+
+        let mut is_pushed = false;
+        loop {
+            //Submission queue is guarded by lock, while completion queue is only accessed by
+            //completion thread.
+            if !is_pushed {
+                is_pushed = unsafe {
+                    self.ring.submission_shared().push(&entry).is_ok()
+                };
+            }
+
+            match self.ring.submit() {
+                Ok(_) => (),
+                Err(error) => match error.raw_os_error() {
+                    Some(libc::EINTR) => (),
+                    //Completion queue is full or kernel is out of resources, both of which are resolved
+                    //as completion thread catches up.
+                    Some(libc::EBUSY) | Some(libc::EAGAIN) => thread::yield_now(),
+                    _ => panic!("Unable to submit io_uring entry: {}", error),
+                },
+            }
+
+            if is_pushed && unsafe { self.ring.submission_shared().is_empty() } {
+                break;
+            }
+        }
+    }
+
+    fn arm(&self, state: &Arc<TimerState>, timeout: time::Duration) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.timers.lock().unwrap_or_else(|error| error.into_inner()).insert(id, (state.clone(), state.generation()));
+
+        //Kernel copies timespec once entry is consumed, which happens before `submit` returns
+        let timespec = types::Timespec::new().sec(timeout.as_secs()).nsec(timeout.subsec_nanos());
+        self.submit(opcode::Timeout::new(&timespec).build().user_data(id));
+
+        id
+    }
+
+    fn disarm(&self, id: u64) {
+        let timer = self.timers.lock().unwrap_or_else(|error| error.into_inner()).remove(&id);
+        //Timeout is still pending only if completion thread hasn't removed it yet
+        if timer.is_some() {
+            self.submit(opcode::TimeoutRemove::new(id).build().user_data(REMOVE));
+        }
+    }
+
+    fn complete(&self, id: u64, result: i32) {
+        if result == -libc::ECANCELED {
+            return;
+        }
+
+        let timer = self.timers.lock().unwrap_or_else(|error| error.into_inner()).remove(&id);
+        //Timer may be restarted after removal, in which case its state is reset and notification is discarded.
+        if let Some((state, generation)) = timer {
+            state.wake_generation(generation);
+        }
+    }
+
+    fn run(&self) {
+        loop {
+            //Only waits for completions, submission is performed by timers
+            let wait = unsafe {
+                self.ring.submitter().enter::<libc::sigset_t>(0, 1, EnterFlags::GETEVENTS.bits(), None)
+            };
+
+            match wait {
+                Ok(_) => (),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => panic!("Unable to wait for io_uring completions: {}", error),
+            }
+
+            for entry in unsafe { self.ring.completion_shared() } {
+                match entry.user_data() {
+                    SHUTDOWN => return,
+                    REMOVE => (),
+                    id => self.complete(id, entry.result()),
+                }
+            }
+        }
+    }
+}
+
+struct Handle(Arc<Inner>);
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        self.0.submit(opcode::Nop::new().build().user_data(SHUTDOWN));
+    }
+}
+
+#[derive(Clone)]
+///Shared `io_uring` instance driving [UringTimer](struct.UringTimer.html)
+///
+///Completions are processed by dedicated thread, which is stopped once all handles to ring,
+///including ones held by timers, are dropped.
+pub struct Ring(Arc<Handle>);
+
+impl Ring {
+    ///Creates new ring with specified number of submission queue entries.
+    pub fn new(entries: u32) -> io::Result<Self> {
+        let inner = Arc::new(Inner {
+            ring: IoUring::new(entries)?,
+            submission: Mutex::new(()),
+            timers: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+        });
+
+        let runner = inner.clone();
+        thread::Builder::new().name("async-timer-uring".into()).spawn(move || runner.run())?;
+
+        Ok(Self(Arc::new(Handle(inner))))
+    }
+
+    ///Returns ring that is used by default.
+    ///
+    ///It is created on first use.
+    pub fn global() -> Self {
+        static GLOBAL: OnceLock<Ring> = OnceLock::new();
+        GLOBAL.get_or_init(|| Ring::new(DEFAULT_ENTRIES).expect("To create io_uring")).clone()
+    }
+
+    #[inline(always)]
+    fn inner(&self) -> &Inner {
+        &(self.0).0
+    }
+}
+
+enum State {
+    Init(time::Duration),
+    Running(u64, Arc<TimerState>),
+}
+
+///Timer based on `io_uring` timeouts
+///
+///Each arming submits `IORING_OP_TIMEOUT` to the [Ring](struct.Ring.html), while cancellation
+///and restart remove pending timeout via `IORING_OP_TIMEOUT_REMOVE`.
+///
+///Callback is invoked from ring's completion thread.
+pub struct UringTimer {
+    ring: Option<Ring>,
+    state: State,
+}
+
+impl UringTimer {
+    #[inline]
+    ///Creates new instance, using global ring.
+    pub const fn new(timeout: time::Duration) -> Self {
+        Self {
+            ring: None,
+            state: State::Init(timeout),
+        }
+    }
+
+    #[inline]
+    ///Creates new instance, using provided ring.
+    pub const fn with_ring(ring: Ring, timeout: time::Duration) -> Self {
+        Self {
+            ring: Some(ring),
+            state: State::Init(timeout),
+        }
+    }
+
+    #[inline]
+    fn ring(&mut self) -> &Inner {
+        self.ring.get_or_insert_with(Ring::global).inner()
+    }
+}
+
+impl super::Timer for UringTimer {
+    #[inline(always)]
+    fn new(timeout: time::Duration) -> Self {
+        assert_time!(timeout);
+        Self::new(timeout)
+    }
+
+    #[inline]
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, ref state) => !state.is_done(),
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running(_, ref state) => state.is_done(),
+        }
+    }
+
+    fn restart(&mut self, new_value: time::Duration) {
+        assert_time!(new_value);
+
+        match self.state {
+            State::Init(ref mut timeout) => {
+                *timeout = new_value;
+            },
+            State::Running(id, ref state) => {
+                let state = state.clone();
+                let ring = self.ring();
+                ring.disarm(id);
+                state.reset();
+                let id = ring.arm(&state, new_value);
+                self.state = State::Running(id, state);
+            }
+        }
+    }
+
+    fn restart_ctx(&mut self, new_value: time::Duration, waker: &task::Waker) {
+        if let State::Running(_, ref state) = self.state {
+            state.register(waker);
+        }
+        self.restart(new_value)
+    }
+
+    fn cancel(&mut self) {
+        if let State::Running(id, ref state) = self.state {
+            state.cancel();
+            self.ring().disarm(id);
+        }
+    }
+}
+
+impl super::SyncTimer for UringTimer {
+    fn init<R, F: Fn(&TimerState) -> R>(&mut self, init: F) -> R {
+        if let State::Init(timeout) = self.state {
+            let state = Arc::new(TimerState::new());
+            init(&state);
+
+            let id = self.ring().arm(&state, timeout);
+            self.state = State::Running(id, state);
+        }
+
+        match &self.state {
+            State::Running(_, ref state) => init(state),
+            State::Init(_) => unreach!(),
+        }
+    }
+}
+
+impl Future for UringTimer {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        crate::timer::poll_sync(self.get_mut(), ctx)
+    }
+}
+
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for UringTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
+impl Drop for UringTimer {
+    fn drop(&mut self) {
+        if let State::Running(id, _) = self.state {
+            self.ring().disarm(id);
+        }
+    }
+}
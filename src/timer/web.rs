@@ -163,6 +163,14 @@ impl Future for WebTimer {
     }
 }
 
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for WebTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
 impl Drop for WebTimer {
     fn drop(&mut self) {
         match self.state {
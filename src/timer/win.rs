@@ -186,6 +186,14 @@ impl Future for WinTimer {
     }
 }
 
+#[cfg(feature = "stream")]
+impl futures_core_03::future::FusedFuture for WinTimer {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        super::Timer::is_expired(self)
+    }
+}
+
 impl Drop for WinTimer {
     fn drop(&mut self) {
         match self.state {
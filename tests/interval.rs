@@ -22,6 +22,197 @@ async fn test_interval() {
     assert!(diff.as_millis() >= 750 && diff.as_millis() <= 1_250);
 }
 
+#[tokio::test]
+async fn test_interval_tick() {
+    let period = time::Duration::from_millis(200);
+
+    let start = time::Instant::now();
+    let mut interval = async_timer::interval_at(start, period);
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, start);
+    assert!(start.elapsed().as_millis() <= 50, "{:?}", start.elapsed());
+
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, start + period);
+    assert!(tick.lateness.as_millis() <= 50, "{:?}", tick.lateness);
+
+    std::thread::sleep(time::Duration::from_millis(100));
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, start + period * 2);
+
+    //Delayed start
+    let start = time::Instant::now() + time::Duration::from_millis(300);
+    let mut interval = Interval::platform_new_at(start, period);
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, start);
+    let diff = time::Instant::now().saturating_duration_since(start);
+    assert!(diff.as_millis() <= 50, "{:?}", diff);
+}
+
+#[tokio::test]
+async fn test_interval_reset() {
+    let period = time::Duration::from_millis(200);
+    let start = time::Instant::now();
+    let mut interval = async_timer::interval_at(start, period);
+    interval.tick().await;
+
+    //Pause, after which schedule restarts from now
+    std::thread::sleep(time::Duration::from_millis(500));
+    let reset = time::Instant::now();
+    interval.reset();
+    let tick = interval.tick().await;
+    assert!(tick.scheduled >= reset + period);
+    assert!(tick.lateness.as_millis() <= 50, "{:?}", tick.lateness);
+
+    let deadline = time::Instant::now() + time::Duration::from_millis(100);
+    interval.reset_at(deadline);
+    assert_eq!(interval.deadline(), deadline);
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, deadline);
+    assert_eq!(interval.deadline(), deadline + period);
+
+    //Pending tick is moved closer
+    interval.set_period(time::Duration::from_millis(100));
+    assert_eq!(interval.interval, time::Duration::from_millis(100));
+    assert_eq!(interval.deadline(), deadline + time::Duration::from_millis(100));
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, deadline + time::Duration::from_millis(100));
+    assert!(tick.lateness.as_millis() <= 50, "{:?}", tick.lateness);
+
+    //Pending tick is moved further
+    interval.set_period(time::Duration::from_millis(300));
+    let tick = interval.tick().await;
+    assert_eq!(tick.scheduled, deadline + time::Duration::from_millis(400));
+    assert!(tick.lateness.as_millis() <= 50, "{:?}", tick.lateness);
+}
+
+#[tokio::test]
+async fn test_interval_drift() {
+    const TICKS: u32 = 100;
+    let period = time::Duration::from_millis(10);
+
+    let start = time::Instant::now();
+    let mut interval = async_timer::interval_at(start, period);
+    //Late tick is caught up instead of being skipped, so that schedule doesn't depend on latency
+    interval.set_missed_tick_behavior(async_timer::MissedTickBehavior::Burst);
+    for idx in 0..=TICKS {
+        let tick = interval.tick().await;
+        assert_eq!(tick.scheduled, start + period * idx);
+    }
+
+    //Latency of individual ticks doesn't accumulate
+    let diff = start.elapsed() - period * TICKS;
+    assert!(diff.as_millis() <= 20, "{:?}", diff);
+}
+
+#[tokio::test]
+async fn test_timer_restart_at() {
+    use async_timer::timer::{Timer, Platform};
+
+    let mut timer = Platform::new(time::Duration::from_secs(10));
+    let deadline = time::Instant::now() + time::Duration::from_millis(200);
+    timer.restart_at(deadline);
+    (&mut timer).await;
+    let diff = time::Instant::now().saturating_duration_since(deadline);
+    assert!(diff.as_millis() <= 50, "{:?}", diff);
+
+    let deadline = time::Instant::now() + time::Duration::from_millis(200);
+    timer.restart_at(deadline);
+    assert!(timer.is_ticking());
+    (&mut timer).await;
+    assert!(time::Instant::now() >= deadline);
+}
+
+#[tokio::test]
+async fn test_interval_aligned() {
+    fn wall_phase(period: time::Duration) -> u128 {
+        let since_epoch = time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap();
+        since_epoch.as_nanos() % period.as_nanos()
+    }
+
+    let period = time::Duration::from_millis(200);
+    let offset = time::Duration::from_millis(50);
+    let max_error = time::Duration::from_millis(30).as_nanos();
+
+    let mut interval = Interval::platform_new_aligned(period, time::Duration::from_secs(0));
+    for _ in 0..3 {
+        let tick = interval.tick().await;
+        let phase = wall_phase(period);
+        assert!(phase <= max_error, "phase={}ns", phase);
+        assert!(tick.lateness.as_nanos() <= max_error);
+    }
+
+    interval.set_period(time::Duration::from_millis(300));
+    interval.tick().await;
+    let phase = wall_phase(time::Duration::from_millis(300));
+    assert!(phase <= max_error, "phase={}ns", phase);
+
+    let mut interval = Interval::platform_new_aligned(period, offset);
+    let before = time::Instant::now();
+    for _ in 0..3 {
+        interval.tick().await;
+        let phase = wall_phase(period);
+        assert!(phase >= offset.as_nanos() && phase <= offset.as_nanos() + max_error, "phase={}ns", phase);
+    }
+    //Each tick happens once per boundary
+    assert!(before.elapsed() >= period * 2);
+
+    //Reset re-aligns to the next boundary
+    std::thread::sleep(time::Duration::from_millis(70));
+    interval.reset();
+    interval.tick().await;
+    let phase = wall_phase(period);
+    assert!(phase >= offset.as_nanos() && phase <= offset.as_nanos() + max_error, "phase={}ns", phase);
+}
+
+#[tokio::test]
+async fn test_interval_missed_tick_behavior() {
+    use async_timer::MissedTickBehavior;
+
+    const PERIOD: time::Duration = time::Duration::from_millis(100);
+
+    async fn late_ticks(behavior: MissedTickBehavior) -> Vec<time::Duration> {
+        let mut interval = Interval::platform_new(PERIOD);
+        assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Skip);
+        interval.set_missed_tick_behavior(behavior);
+
+        let start = time::Instant::now();
+        interval.wait().await;
+        //Miss ticks at 200ms and 300ms
+        std::thread::sleep(time::Duration::from_millis(250));
+
+        let mut ticks = Vec::new();
+        for _ in 0..3 {
+            interval.wait().await;
+            ticks.push(start.elapsed());
+        }
+        ticks
+    }
+
+    fn assert_near(tick: time::Duration, expected_ms: u128) {
+        assert!(tick.as_millis() >= expected_ms.saturating_sub(20) && tick.as_millis() <= expected_ms + 40, "{:?} != {}ms", tick, expected_ms);
+    }
+
+    //Catches up on 200ms and 300ms immediately
+    let ticks = late_ticks(MissedTickBehavior::Burst).await;
+    assert_near(ticks[0], 350);
+    assert_near(ticks[1], 350);
+    assert_near(ticks[2], 400);
+
+    //Restarts period from late tick
+    let ticks = late_ticks(MissedTickBehavior::Delay).await;
+    assert_near(ticks[0], 350);
+    assert_near(ticks[1], 450);
+    assert_near(ticks[2], 550);
+
+    //Skips 300ms, keeping original schedule
+    let ticks = late_ticks(MissedTickBehavior::Skip).await;
+    assert_near(ticks[0], 350);
+    assert_near(ticks[1], 400);
+    assert_near(ticks[2], 500);
+}
+
+#[cfg(all(any(feature = "tokio1", feature = "async-io"), target_os = "linux"))]
 async fn test_interval_average(num_runs: usize, interval: time::Duration) {
     const ACCURACY: time::Duration = time::Duration::from_nanos(133333);
 
@@ -71,3 +262,101 @@ async fn test_average_of_small_interval() {
 async fn test_average_of_mid_interval() {
     test_interval_average(60, time::Duration::from_secs_f32(135. / 120.)).await;
 }
+
+#[test]
+#[cfg(all(feature = "async-io", not(feature = "tokio1")))]
+#[cfg(target_os = "linux")]
+fn test_async_io_average_of_small_interval() {
+    async_io_2::block_on(test_interval_average(6000, time::Duration::from_secs_f32(1. / 120.)));
+}
+
+#[test]
+#[cfg(all(feature = "async-io", not(feature = "tokio1")))]
+#[cfg(target_os = "linux")]
+fn test_async_io_average_of_mid_interval() {
+    async_io_2::block_on(test_interval_average(60, time::Duration::from_secs_f32(135. / 120.)));
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_interval_stream() {
+    use futures_core_03::Stream;
+    use futures_core_03::stream::FusedStream;
+
+    let mut interval = Interval::platform_new(time::Duration::from_millis(250));
+    assert!(!interval.is_terminated());
+
+    let before = time::Instant::now();
+    let mut scheduled = Vec::new();
+    for _ in 0..3 {
+        let tick = std::future::poll_fn(|ctx| core::pin::Pin::new(&mut interval).poll_next(ctx)).await;
+        scheduled.push(tick.expect("Interval never ends"));
+    }
+    let diff = before.elapsed();
+    assert!(diff.as_millis() >= 700 && diff.as_millis() <= 1_000, "{:?}", diff);
+
+    for ticks in scheduled.windows(2) {
+        let period = ticks[1] - ticks[0];
+        assert!(period.as_millis() >= 200 && period.as_millis() <= 300, "{:?}", period);
+    }
+}
+
+#[test]
+fn test_sync_interval() {
+    use async_timer::{MissedTickBehavior, SyncInterval};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    //Callback may be invoked from signal handler
+    static TICKS: AtomicUsize = AtomicUsize::new(0);
+    fn on_tick() {
+        TICKS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn wait_tick(timeout: time::Duration) -> bool {
+        let ticks = TICKS.load(Ordering::SeqCst);
+        let before = time::Instant::now();
+        while before.elapsed() < timeout {
+            if TICKS.load(Ordering::SeqCst) != ticks {
+                return true;
+            }
+            std::thread::sleep(time::Duration::from_millis(1));
+        }
+        false
+    }
+
+    let period = time::Duration::from_millis(100);
+
+    let mut interval = SyncInterval::platform_new(period);
+    assert!(!interval.is_running());
+    assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Skip);
+    interval.register(on_tick as fn());
+
+    //Not started yet
+    assert!(!wait_tick(time::Duration::from_millis(300)));
+
+    let start = time::Instant::now();
+    interval.start();
+    assert!(interval.is_running());
+    for idx in 1..=5u32 {
+        assert!(wait_tick(time::Duration::from_secs(1)));
+        assert!(start.elapsed() >= period * idx - time::Duration::from_millis(10));
+    }
+    //Ticks are scheduled relative to start, so there is no drift.
+    assert!(start.elapsed() <= period * 5 + time::Duration::from_millis(250));
+
+    interval.set_period(time::Duration::from_millis(50));
+    assert_eq!(interval.interval(), time::Duration::from_millis(50));
+    assert!(wait_tick(time::Duration::from_secs(1)));
+
+    interval.cancel();
+    assert!(!interval.is_running());
+    assert!(!wait_tick(time::Duration::from_millis(300)));
+
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Delay);
+    interval.start();
+    assert!(wait_tick(time::Duration::from_secs(1)));
+    drop(interval);
+
+    assert!(!wait_tick(time::Duration::from_millis(300)));
+}
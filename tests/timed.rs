@@ -20,3 +20,77 @@ async fn test_timed() {
 
     assert!(diff.as_millis() >= 3_500 && diff.as_millis() <= 4_500);
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_cpu_timed() {
+    use core::task;
+
+    //Timer consumes no CPU time while waiting
+    let mut future = async_timer::new_timer(time::Duration::from_millis(500));
+    let work = async_timer::cpu_timed(Pin::new(&mut future), time::Duration::from_millis(50));
+    assert!(work.await.is_ok());
+
+    let mut busy = core::future::poll_fn(|ctx: &mut task::Context| {
+        let start = time::Instant::now();
+        while start.elapsed() < time::Duration::from_millis(5) {
+            core::hint::spin_loop();
+        }
+        ctx.waker().wake_by_ref();
+        task::Poll::<()>::Pending
+    });
+    let work = async_timer::cpu_timed(Pin::new(&mut busy), time::Duration::from_millis(50));
+    let expired = work.await.unwrap_err();
+    assert!(expired.consumed >= expired.limit);
+    assert!(expired.consumed < time::Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_timeout() {
+    let work = async_timer::timeout(async_timer::new_timer(time::Duration::from_secs(3)), time::Duration::from_secs(1));
+    assert_eq!(work.timeout(), time::Duration::from_secs(1));
+
+    let before = time::SystemTime::now();
+    let error = work.await.unwrap_err();
+    let diff = time::SystemTime::now().duration_since(before).unwrap();
+    assert_eq!(error.timeout(), time::Duration::from_secs(1));
+    assert!(diff.as_millis() >= 750 && diff.as_millis() <= 1_250);
+
+    //Owned future can be spawned
+    let work = tokio::spawn(async_timer::timeout(async {
+        async_timer::new_timer(time::Duration::from_millis(500)).await;
+        true
+    }, time::Duration::from_secs(2)));
+
+    assert!(work.await.unwrap().unwrap());
+}
+
+#[tokio::test]
+async fn test_future_ext() {
+    use async_timer::FutureExt;
+    use async_timer::timer::Platform;
+
+    let work = Platform::new(time::Duration::from_secs(3)).timeout(time::Duration::from_millis(500));
+    assert!(work.await.is_err());
+
+    let deadline = time::Instant::now() + time::Duration::from_millis(500);
+    let work = Platform::new(time::Duration::from_secs(3)).timeout_at(deadline);
+    //Time before first poll counts towards deadline
+    std::thread::sleep(time::Duration::from_millis(300));
+    assert!(work.await.is_err());
+    let diff = time::Instant::now().saturating_duration_since(deadline);
+    assert!(diff.as_millis() <= 150, "{:?}", diff);
+
+    let before = time::Instant::now();
+    let work = async {
+        before.elapsed()
+    }.delay_start(time::Duration::from_millis(500));
+    assert!(!work.is_started());
+    let started = work.await;
+    assert!(started.as_millis() >= 450 && started.as_millis() <= 750, "{:?}", started);
+
+    let work = async {
+        true
+    }.delay_start_with::<Platform>(time::Duration::from_millis(100)).timeout(time::Duration::from_secs(1));
+    assert!(work.await.unwrap());
+}
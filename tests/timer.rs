@@ -17,6 +17,52 @@ async fn test_async_timer() {
     assert!(diff.as_millis() >= 1_500 && diff.as_millis() <= 2_500);
 }
 
+#[tokio::test]
+async fn test_sleep() {
+    let before = time::Instant::now();
+    let sleep = async_timer::sleep(time::Duration::from_millis(250));
+    //Time before first poll counts towards deadline
+    std::thread::sleep(time::Duration::from_millis(100));
+    assert!(!sleep.is_elapsed());
+    sleep.await;
+    let diff = before.elapsed();
+    assert!(diff.as_millis() >= 200 && diff.as_millis() <= 400, "{:?}", diff);
+
+    let sleep = async_timer::sleep_until(time::Instant::now() + time::Duration::from_millis(250));
+    tokio::pin!(sleep);
+    (&mut sleep).await;
+    assert!(sleep.is_elapsed());
+
+    let deadline = sleep.deadline() + time::Duration::from_millis(250);
+    sleep.as_mut().reset(deadline);
+    assert_eq!(sleep.deadline(), deadline);
+    assert!(!sleep.is_elapsed());
+    (&mut sleep).await;
+    let diff = time::Instant::now().saturating_duration_since(deadline);
+    assert!(diff.as_millis() <= 150, "{:?}", diff);
+
+    //Elapsed deadline resolves as soon as possible
+    sleep.as_mut().reset(time::Instant::now() - time::Duration::from_secs(1));
+    sleep.await;
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_timer_fused() {
+    use futures_core_03::future::FusedFuture;
+
+    let mut work = Platform::new(time::Duration::from_millis(100));
+    assert!(!work.is_terminated());
+    (&mut work).await;
+    assert!(work.is_terminated());
+
+    let mut work = new_sync_timer(time::Duration::from_secs(10));
+    assert!(!work.tick());
+    assert!(!work.is_terminated());
+    work.cancel();
+    assert!(work.is_terminated());
+}
+
 #[test]
 fn test_cancel_timer() {
     let mut work = new_sync_timer(time::Duration::from_secs(500000));
@@ -34,3 +80,623 @@ fn test_cancel_timer() {
     assert!(!work.is_ticking());
     assert!(work.is_expired());
 }
+
+#[test]
+fn test_sync_timer_callbacks() {
+    use async_timer::state::TimerState;
+
+    use std::sync::{mpsc, Arc};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    //These callbacks are not async-signal-safe, so they are notified from regular thread
+    fn notify(state: &Arc<TimerState>) {
+        let state = state.clone();
+        std::thread::spawn(move || state.wake());
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let state = Arc::new(TimerState::new());
+    state.register(sender.clone());
+    notify(&state);
+    receiver.recv_timeout(time::Duration::from_secs(5)).expect("To receive notification");
+    assert!(state.is_done());
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let callback = {
+        let counter = counter.clone();
+        Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }) as Arc<dyn Fn() + Send + Sync>
+    };
+    let state = Arc::new(TimerState::new());
+    state.register(callback.clone());
+    notify(&state);
+    let before = time::Instant::now();
+    while counter.load(Ordering::SeqCst) == 0 && before.elapsed() < time::Duration::from_secs(5) {
+        std::thread::sleep(time::Duration::from_millis(1));
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+    let state = Arc::new(TimerState::new());
+    state.register(std::thread::current());
+    notify(&state);
+    let before = time::Instant::now();
+    while !state.is_done() && before.elapsed() < time::Duration::from_secs(5) {
+        std::thread::park_timeout(time::Duration::from_secs(1));
+    }
+    assert!(state.is_done());
+}
+
+#[tokio::test]
+async fn test_backend_timer() {
+    use async_timer::timer::{TimerBackend, BackendTimer};
+    use async_timer::state::TimerState;
+
+    use std::sync::Arc;
+
+    struct ThreadBackend {
+        state: Arc<TimerState>,
+    }
+
+    impl TimerBackend for ThreadBackend {
+        fn create(state: Arc<TimerState>) -> Self {
+            Self {
+                state,
+            }
+        }
+
+        fn arm(&mut self, timeout: time::Duration) {
+            let state = self.state.clone();
+            let generation = state.generation();
+            std::thread::spawn(move || {
+                std::thread::sleep(timeout);
+                state.wake_generation(generation);
+            });
+        }
+
+        fn disarm(&mut self) {
+        }
+    }
+
+    let mut work = BackendTimer::<ThreadBackend>::new(time::Duration::from_millis(250));
+    assert!(!work.is_ticking());
+    assert!(work.backend().is_none());
+
+    let before = time::Instant::now();
+    (&mut work).await;
+    let diff = before.elapsed();
+    assert!(work.is_expired());
+    assert!(diff.as_millis() >= 200 && diff.as_millis() <= 500, "{:?}", diff);
+
+    //Restart disarms previous timeout
+    work.restart(time::Duration::from_millis(100));
+    work.restart(time::Duration::from_millis(300));
+    let before = time::Instant::now();
+    (&mut work).await;
+    assert!(before.elapsed() >= time::Duration::from_millis(250));
+
+    work.restart(time::Duration::from_secs(10));
+    assert!(!work.tick());
+    work.cancel();
+    assert!(work.is_expired());
+}
+
+#[cfg(all(feature = "async-io", unix))]
+#[test]
+fn test_async_io_timer() {
+    use async_timer::timer::AsyncIoTimer;
+
+    let work = AsyncIoTimer::new(time::Duration::from_secs(2));
+    assert!(!work.is_ticking());
+    assert!(!work.is_expired());
+
+    let before = time::SystemTime::now();
+    async_io_2::block_on(work);
+    let after = time::SystemTime::now();
+    let diff = after.duration_since(before).unwrap();
+
+    assert!(diff.as_millis() >= 1_500 && diff.as_millis() <= 2_500);
+}
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+#[tokio::test]
+async fn test_uring_timer() {
+    use async_timer::timer::{UringTimer, Ring};
+
+    let ring = Ring::new(8).expect("To create io_uring");
+    let first = UringTimer::with_ring(ring.clone(), time::Duration::from_secs(2));
+    let mut second = UringTimer::with_ring(ring, time::Duration::from_secs(500000));
+
+    assert!(!second.tick());
+    assert!(second.is_ticking());
+
+    let before = time::SystemTime::now();
+    first.await;
+    let after = time::SystemTime::now();
+    let diff = after.duration_since(before).unwrap();
+
+    assert!(diff.as_millis() >= 1_500 && diff.as_millis() <= 2_500);
+
+    assert!(second.is_ticking());
+    second.cancel();
+    assert!(!second.is_ticking());
+    assert!(second.is_expired());
+
+    second.restart(time::Duration::from_secs(1));
+    assert!(second.is_ticking());
+    (&mut second).await;
+    assert!(second.is_expired());
+}
+
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+#[tokio::test]
+async fn test_reactor_timer() {
+    use async_timer::timer::{Reactor, ReactorTimer};
+    use std::os::unix::io::{AsRawFd, RawFd};
+    use core::task;
+
+    struct Fd(RawFd);
+
+    impl AsRawFd for Fd {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0
+        }
+    }
+
+    struct TokioReactor(tokio::io::unix::AsyncFd<Fd>);
+
+    impl Reactor for TokioReactor {
+        fn register(fd: RawFd) -> std::io::Result<Self> {
+            #[allow(deprecated)]
+            tokio::io::unix::AsyncFd::with_interest(Fd(fd), tokio::io::Interest::READABLE).map(Self)
+        }
+
+        fn poll_read_ready(&mut self, ctx: &mut task::Context) -> task::Poll<std::io::Result<()>> {
+            match self.0.poll_read_ready(ctx) {
+                task::Poll::Ready(Ok(mut guard)) => {
+                    guard.clear_ready();
+                    task::Poll::Ready(Ok(()))
+                },
+                task::Poll::Ready(Err(error)) => task::Poll::Ready(Err(error)),
+                task::Poll::Pending => task::Poll::Pending,
+            }
+        }
+
+        fn deregister(self) {
+        }
+    }
+
+    let mut work = ReactorTimer::<TokioReactor>::new(time::Duration::from_secs(2));
+    assert!(!work.is_ticking());
+    assert!(!work.is_expired());
+
+    let before = time::SystemTime::now();
+    (&mut work).await;
+    let after = time::SystemTime::now();
+    let diff = after.duration_since(before).unwrap();
+
+    assert!(diff.as_millis() >= 1_500 && diff.as_millis() <= 2_500);
+    assert!(work.is_expired());
+
+    work.restart(time::Duration::from_secs(1));
+    assert!(work.is_ticking());
+    work.await;
+}
+
+#[cfg(all(feature = "mio", any(target_os = "linux", target_os = "android")))]
+#[test]
+fn test_mio_timer() {
+    use async_timer::timer::MioTimer;
+    use mio_1 as mio;
+
+    let mut poll = mio::Poll::new().expect("To create poll");
+    let mut events = mio::Events::with_capacity(8);
+
+    let mut timer = MioTimer::new(mio::Token(1));
+    timer.register(poll.registry()).expect("To register timer");
+    assert_eq!(timer.token(), mio::Token(1));
+    assert_eq!(timer.read(), 0);
+
+    let before = time::Instant::now();
+    timer.set(time::Duration::from_millis(500));
+    poll.poll(&mut events, Some(time::Duration::from_secs(2))).expect("To poll");
+    let diff = before.elapsed();
+
+    assert!(events.iter().any(|event| timer.is_expired_by(event)));
+    assert!(diff.as_millis() >= 400 && diff.as_millis() <= 1_000);
+    assert_eq!(timer.read(), 1);
+
+    let before = time::Instant::now();
+    timer.set_at(before + time::Duration::from_millis(500));
+    poll.poll(&mut events, Some(time::Duration::from_secs(2))).expect("To poll");
+    let diff = before.elapsed();
+
+    assert!(events.iter().any(|event| timer.is_expired_by(event)));
+    assert!(diff.as_millis() >= 500 && diff.as_millis() <= 1_000);
+    assert_eq!(timer.read(), 1);
+
+    timer.set(time::Duration::from_millis(100));
+    timer.unset();
+    poll.poll(&mut events, Some(time::Duration::from_millis(300))).expect("To poll");
+    assert!(events.is_empty());
+
+    timer.deregister(poll.registry()).expect("To deregister timer");
+}
+
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+#[tokio::test]
+async fn test_epoll_timer() {
+    use async_timer::timer::EpollTimer;
+
+    let mut work = EpollTimer::new(time::Duration::from_secs(1));
+    assert!(!work.is_ticking());
+    assert!(!work.is_expired());
+
+    let before = time::SystemTime::now();
+    (&mut work).await;
+    let after = time::SystemTime::now();
+    let diff = after.duration_since(before).unwrap();
+
+    assert!(diff.as_millis() >= 750 && diff.as_millis() <= 1_250);
+    assert!(work.is_expired());
+
+    let before = time::SystemTime::now();
+    work.restart(time::Duration::from_secs(1));
+    assert!(work.is_ticking());
+    (&mut work).await;
+    let after = time::SystemTime::now();
+    let diff = after.duration_since(before).unwrap();
+
+    assert!(diff.as_millis() >= 750 && diff.as_millis() <= 1_250);
+    assert!(work.is_expired());
+}
+
+#[cfg(all(feature = "tokio1", unix))]
+#[test]
+fn test_tokio_timer_pool() {
+    use async_timer::timer::{TimerPool, PoolStats};
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread().enable_io().build().expect("To build runtime")
+    }
+
+    let pool = TimerPool::new(1);
+
+    runtime().block_on(async {
+        for idx in 0..3 {
+            let work = pool.timer(time::Duration::from_millis(250));
+
+            let before = time::SystemTime::now();
+            work.await;
+            let after = time::SystemTime::now();
+            let diff = after.duration_since(before).unwrap();
+
+            assert!(diff.as_millis() >= 200 && diff.as_millis() <= 500, "{}: {:?}", idx, diff);
+        }
+
+        assert_eq!(pool.stats(), PoolStats { hits: 2, misses: 1, idle: 1 });
+
+        let first = pool.timer(time::Duration::from_millis(250));
+        let second = pool.timer(time::Duration::from_millis(250));
+        //Only one fd is idle, so second timer has to create its own, which is discarded afterwards.
+        tokio::join!(first, second);
+        assert_eq!(pool.stats(), PoolStats { hits: 3, misses: 2, idle: 1 });
+    });
+
+    //Idle fd is registered with previous runtime, which is shut down, so it is not re-used
+    runtime().block_on(async {
+        let work = pool.timer(time::Duration::from_millis(250));
+        let before = time::Instant::now();
+        work.await;
+        let diff = before.elapsed();
+        assert!(diff.as_millis() >= 200 && diff.as_millis() <= 500, "{:?}", diff);
+    });
+    assert_eq!(pool.stats(), PoolStats { hits: 3, misses: 3, idle: 1 });
+}
+
+#[cfg(all(feature = "tokio1", unix))]
+#[test]
+fn test_tokio_timer_without_io() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().expect("To build runtime");
+
+    //Second timer uses remembered absence of IO driver
+    for _ in 0..2 {
+        let work = Platform::new(time::Duration::from_millis(250));
+        let before = time::Instant::now();
+        runtime.block_on(work);
+        let diff = before.elapsed();
+        assert!(diff.as_millis() >= 200 && diff.as_millis() <= 500, "{:?}", diff);
+    }
+}
+
+#[cfg(all(feature = "tokio1", unix))]
+#[test]
+fn test_tokio_timer_without_runtime() {
+    use std::sync::Arc;
+    use std::future::Future;
+    use std::task::{Context, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    struct Unparker(Thread);
+
+    impl Wake for Unparker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unparker(thread::current())));
+    let mut ctx = Context::from_waker(&waker);
+
+    let mut work = Platform::new(time::Duration::from_millis(250));
+    let before = time::Instant::now();
+    while Future::poll(core::pin::Pin::new(&mut work), &mut ctx).is_pending() {
+        thread::park_timeout(time::Duration::from_secs(1));
+    }
+    let diff = before.elapsed();
+
+    assert!(work.is_expired());
+    assert!(diff.as_millis() >= 200 && diff.as_millis() <= 500, "{:?}", diff);
+}
+
+#[cfg(feature = "custom")]
+#[tokio::test]
+async fn test_custom_timer() {
+    use async_timer::Interval;
+    use async_timer::timer::CustomTimer;
+    use async_timer::timer::custom::{self, Hooks};
+
+    use std::sync::OnceLock;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static START: OnceLock<time::Instant> = OnceLock::new();
+    static ALARM: AtomicU64 = AtomicU64::new(u64::MAX);
+
+    fn now() -> u64 {
+        START.get_or_init(time::Instant::now).elapsed().as_micros() as u64
+    }
+
+    fn arm(tick: u64) {
+        ALARM.store(tick, Ordering::Release);
+    }
+
+    custom::init(Hooks {
+        frequency: 1_000_000,
+        now,
+        arm,
+    });
+
+    //Emulates hardware compare interrupt
+    std::thread::spawn(|| loop {
+        if now() >= ALARM.load(Ordering::Acquire) {
+            ALARM.store(u64::MAX, Ordering::Release);
+            custom::on_alarm();
+        }
+        std::thread::sleep(time::Duration::from_micros(100));
+    });
+
+    let mut work = CustomTimer::new(time::Duration::from_secs(1));
+    let mut cancelled = CustomTimer::new(time::Duration::from_millis(500));
+    assert!(!work.is_ticking());
+    assert!(!cancelled.tick());
+    cancelled.cancel();
+
+    let before = time::Instant::now();
+    (&mut work).await;
+    let diff = before.elapsed();
+
+    assert!(diff.as_millis() >= 1_000 && diff.as_millis() <= 1_250);
+    assert!(work.is_expired());
+    assert!(cancelled.is_expired());
+
+    let mut interval = Interval::<CustomTimer>::new(time::Duration::from_millis(250));
+    let before = time::Instant::now();
+    for _ in 0..4 {
+        interval.wait().await;
+    }
+    let diff = before.elapsed();
+
+    assert!(diff.as_millis() >= 1_000 && diff.as_millis() <= 1_250);
+
+    //Callback is allowed to restart timers
+    let other = std::sync::Arc::new(std::sync::Mutex::new(CustomTimer::new(time::Duration::from_secs(10))));
+    assert!(!other.lock().unwrap().tick());
+
+    let mut trigger = CustomTimer::new(time::Duration::from_millis(100));
+    let restarted = other.clone();
+    let callback: std::sync::Arc<dyn Fn() + Send + Sync> = std::sync::Arc::new(move || {
+        restarted.lock().unwrap().restart(time::Duration::from_millis(100));
+    });
+    trigger.init(|state| state.register(callback.clone()));
+
+    std::thread::sleep(time::Duration::from_millis(500));
+    assert!(trigger.is_expired());
+    assert!(other.lock().unwrap().is_expired());
+
+    //SyncInterval re-arms timer from within alarm
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut interval = async_timer::SyncInterval::<CustomTimer>::new(time::Duration::from_millis(100));
+    interval.register(sender);
+    let before = time::Instant::now();
+    interval.start();
+    for _ in 0..3 {
+        receiver.recv_timeout(time::Duration::from_secs(1)).expect("To receive tick");
+    }
+    let diff = before.elapsed();
+    assert!(diff.as_millis() >= 290 && diff.as_millis() <= 500, "{:?}", diff);
+    interval.cancel();
+    drop(interval);
+}
+
+#[test]
+fn test_sync_timer_wait_blocking() {
+    let mut work = new_sync_timer(time::Duration::from_millis(250));
+
+    let before = time::Instant::now();
+    work.wait_blocking();
+    let diff = before.elapsed();
+    assert!(work.is_expired());
+    assert!(diff.as_millis() >= 200 && diff.as_millis() <= 500, "{:?}", diff);
+
+    work.restart(time::Duration::from_secs(10));
+    let before = time::Instant::now();
+    assert!(!work.wait_blocking_timeout(time::Duration::from_millis(100)));
+    assert!(before.elapsed() >= time::Duration::from_millis(100));
+    assert!(work.is_ticking());
+
+    work.restart(time::Duration::from_millis(100));
+    assert!(work.wait_blocking_timeout(time::Duration::from_secs(10)));
+    assert!(work.is_expired());
+}
+
+async fn poll_once<F: core::future::Future + Unpin>(future: &mut F) -> core::task::Poll<F::Output> {
+    core::future::poll_fn(|ctx| core::task::Poll::Ready(core::pin::Pin::new(&mut *future).poll(ctx))).await
+}
+
+#[tokio::test]
+async fn test_precision_timer() {
+    use async_timer::timer::{PrecisionTimer, SpinMode};
+
+    const RUNS: usize = 21;
+
+    for mode in [SpinMode::Spin, SpinMode::Yield] {
+        let mut work = PrecisionTimer::platform_new(time::Duration::from_millis(10), time::Duration::from_millis(5), mode);
+        assert!(!work.is_ticking());
+        assert!(work.error().is_none());
+
+        let mut errors = Vec::with_capacity(RUNS);
+        for idx in 0..RUNS {
+            let before = time::Instant::now();
+            if idx > 0 {
+                work.restart(time::Duration::from_millis(10));
+            }
+            (&mut work).await;
+            assert!(before.elapsed() >= time::Duration::from_millis(10));
+            assert!(work.is_expired());
+            errors.push(work.error().expect("To have error once expired"));
+        }
+
+        //Test threads compete for CPU, so some runs may be preempted right before deadline,
+        //but typical expiration must be within 100us.
+        errors.sort();
+        let median = errors[RUNS / 2];
+        assert!(median < time::Duration::from_micros(100), "{:?}: median error={:?}", mode, median);
+
+        //Whole timeout is within threshold
+        let before = time::Instant::now();
+        work.restart(time::Duration::from_millis(2));
+        assert!(work.is_ticking());
+        (&mut work).await;
+        assert!(before.elapsed() >= time::Duration::from_millis(2));
+
+        //Cancel while waiting for underlying timer
+        let mut work = PrecisionTimer::platform_new(time::Duration::from_secs(1), time::Duration::from_millis(5), mode);
+        assert!(poll_once(&mut work).await.is_pending());
+        work.cancel();
+        assert!(work.is_expired());
+        (&mut work).await;
+    }
+}
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
+#[test]
+fn test_posix_cpu_timer() {
+    use async_timer::timer::{PosixTimer, Clock};
+
+    let mut work = PosixTimer::with_clock(Clock::THREAD_CPUTIME, time::Duration::from_millis(100));
+    assert!(!work.tick());
+
+    let start = time::Instant::now();
+    while !work.is_expired() && start.elapsed() < time::Duration::from_secs(5) {
+        core::hint::spin_loop();
+    }
+
+    let diff = start.elapsed();
+    assert!(work.is_expired());
+    assert!(diff.as_millis() >= 90 && diff.as_millis() <= 1_000, "{:?}", diff);
+
+    assert!(Clock::thread(unsafe { libc::pthread_self() }).is_ok());
+}
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
+#[test]
+fn test_posix_timer_fork() {
+    use async_timer::timer::PosixTimer;
+
+    let mut work = PosixTimer::new(time::Duration::from_millis(200));
+    assert!(!work.tick());
+    assert!(work.is_ticking());
+
+    let pid = unsafe { libc::fork() };
+    assert_ne!(pid, -1);
+
+    if pid == 0 {
+        //Inherited timer must be re-created and expire in child
+        let start = time::Instant::now();
+        while !work.tick() && start.elapsed() < time::Duration::from_secs(5) {
+            std::thread::sleep(time::Duration::from_millis(1));
+        }
+        let inherited = work.is_expired();
+
+        work.restart(time::Duration::from_millis(50));
+        let start = time::Instant::now();
+        while !work.tick() && start.elapsed() < time::Duration::from_secs(5) {
+            std::thread::sleep(time::Duration::from_millis(1));
+        }
+        let restarted = work.is_expired();
+        drop(work);
+
+        unsafe {
+            libc::_exit(if inherited && restarted { 0 } else { 1 });
+        }
+    }
+
+    let mut status = 0;
+    assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+    assert!(libc::WIFEXITED(status));
+    assert_eq!(libc::WEXITSTATUS(status), 0);
+
+    let start = time::Instant::now();
+    while !work.tick() && start.elapsed() < time::Duration::from_secs(5) {
+        std::thread::sleep(time::Duration::from_millis(1));
+    }
+    assert!(work.is_expired());
+}
+
+#[tokio::test]
+async fn test_calibrated_timer() {
+    use async_timer::timer::{CalibratedTimer, Calibrator};
+
+    static CALIBRATOR: Calibrator = Calibrator::new();
+
+    assert_eq!(CALIBRATOR.latency(), time::Duration::from_secs(0));
+    CALIBRATOR.calibrate::<Platform>(5, time::Duration::from_millis(10)).await;
+    let latency = CALIBRATOR.latency();
+    assert!(latency < time::Duration::from_millis(50), "latency={:?}", latency);
+
+    let mut work = CalibratedTimer::platform_new(&CALIBRATOR, time::Duration::from_millis(100));
+    assert!(!work.is_ticking());
+    assert!(work.deadline().is_none());
+
+    let before = time::Instant::now();
+    (&mut work).await;
+    let diff = before.elapsed();
+    assert!(work.is_expired());
+    assert!(diff.as_millis() >= 50 && diff.as_millis() <= 200, "{:?}", diff);
+    work.cancel();
+    assert!(work.is_expired());
+
+    //Cancel while waiting for underlying timer
+    let mut work = CalibratedTimer::platform_new(&CALIBRATOR, time::Duration::from_secs(1));
+    assert!(poll_once(&mut work).await.is_pending());
+    assert!(work.is_ticking());
+    work.cancel();
+    assert!(work.is_expired());
+    (&mut work).await;
+
+    CALIBRATOR.reset();
+    CALIBRATOR.record(time::Duration::from_millis(8));
+    assert_eq!(CALIBRATOR.latency(), time::Duration::from_millis(8));
+    CALIBRATOR.record(time::Duration::from_millis(0));
+    assert_eq!(CALIBRATOR.latency(), time::Duration::from_millis(7));
+}